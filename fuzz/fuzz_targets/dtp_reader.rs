@@ -0,0 +1,13 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rossy_diac::business::dtp_converter::converter::{self, ArrayPadding};
+use rossy_diac::business::dtp_converter::dtp_reader;
+
+fuzz_target!(|content: &[u8]| {
+    if let Ok(data_type) = dtp_reader::parse(content) {
+        for array_padding in [ArrayPadding::Pad, ArrayPadding::Reject] {
+            let _ = converter::convert("fuzzed_msgs", &data_type, array_padding);
+        }
+    }
+});
@@ -0,0 +1,9 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rossy_diac::business::msg_converter::msg_reader;
+
+fuzz_target!(|content: &str| {
+    let _ = msg_reader::parse("Fuzzed".to_string(), content);
+    let _ = msg_reader::parse_service("Fuzzed".to_string(), content);
+});
@@ -1,12 +1,13 @@
 use clap::error::ErrorKind;
-use clap::{CommandFactory, Parser, Subcommand};
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
 use log::debug;
 
-use crate::business::error::Error;
-use crate::business::handler::*;
+use rossy_diac::business::error::Error;
+use rossy_diac::business::handler::*;
 
 /// A simple-to-use converter prototype.
-/// It converts MSG files to DTP files and vice versa.
+/// It converts MSG and IDL files to DTP files and vice versa.
+/// SRV files are converted to DTP files and an adapter type.
 #[derive(Parser)]
 #[command(version, about, long_about = None)]
 struct Cli {
@@ -16,17 +17,42 @@ struct Cli {
 
 #[derive(Subcommand, Debug)]
 enum Command {
-    /// Converts a MSG file to a DTP file
+    /// Converts MSG, IDL or SRV files to DTP files
     ConvertToDtp {
-        /// The file to convert
-        #[arg(short = 'f', long = "file")]
-        path_to_msg_file: String,
+        /// The files to convert, IDL and SRV files are recognized by their extension
+        #[arg(short = 'f', long = "file", required = true, num_args = 1..)]
+        paths_to_msg_files: Vec<String>,
         /// The directory where the conversion result will be written
         #[arg(short = 'd', long = "destination-directory")]
         path_to_destination_directory: String,
         /// The name of the package the source file belongs to
         #[arg(short = 'p', long = "package-name")]
         package_name: String,
+        /// Also generate publisher and subscriber function block types for each message type
+        #[arg(long = "function-blocks")]
+        generate_function_blocks: bool,
+        /// Also generate C++ functions converting between the FORTE and the rclcpp type of each message type
+        #[arg(long = "forte-code")]
+        generate_forte_code: bool,
+        /// Write the results into a 4diac type library named after the package
+        #[arg(long = "typelib")]
+        typelib: bool,
+        /// The author recorded in the version info of the generated types
+        #[arg(long = "author")]
+        author: Option<String>,
+        /// The organization recorded in the version info of the generated types
+        #[arg(long = "organization")]
+        organization: Option<String>,
+        /// The version recorded in the version info of the generated types and the type library
+        #[arg(long = "type-version", default_value = DEFAULT_TYPE_VERSION)]
+        type_version: String,
+        /// How the package is encoded into the names of the generated types
+        #[arg(long = "type-naming", value_enum, default_value_t = TypeNamingArg::Mangled)]
+        type_naming: TypeNamingArg,
+        /// Skip source files which are unchanged since the last incremental conversion into the destination directory
+        /// and whose converted files still exist, as recorded in its manifest file
+        #[arg(long = "incremental")]
+        incremental: bool,
     },
     /// Converts a DTP file to a MSG file
     ConvertToMsg {
@@ -39,21 +65,156 @@ enum Command {
         /// The name of the package the destination file will belong to
         #[arg(short = 'p', long = "package-name")]
         package_name: String,
+        /// How static arrays with partial initial values are handled
+        #[arg(long = "array-padding", value_enum, default_value_t = ArrayPaddingArg::Pad)]
+        array_padding: ArrayPaddingArg,
+    },
+    /// Converts a DTP file to an IDL file
+    ConvertToIdl {
+        /// The file to convert
+        #[arg(short = 'f', long = "file")]
+        path_to_dtp_file: String,
+        /// The directory where the conversion result will be written
+        #[arg(short = 'd', long = "destination-directory")]
+        path_to_destination_directory: String,
+        /// The name of the package the destination file will belong to
+        #[arg(short = 'p', long = "package-name")]
+        package_name: String,
+        /// How static arrays with partial initial values are handled
+        #[arg(long = "array-padding", value_enum, default_value_t = ArrayPaddingArg::Pad)]
+        array_padding: ArrayPaddingArg,
+    },
+    /// Converts a MSG, IDL, SRV or DTP file there and back and reports every change of its meaning
+    VerifyRoundtrip {
+        /// The file to verify, the conversion direction is chosen by its extension
+        #[arg(short = 'f', long = "file")]
+        path_to_file: String,
+        /// The name of the package the file belongs to
+        #[arg(short = 'p', long = "package-name")]
+        package_name: String,
+        /// How static arrays with partial initial values are handled
+        #[arg(long = "array-padding", value_enum, default_value_t = ArrayPaddingArg::Pad)]
+        array_padding: ArrayPaddingArg,
+    },
+    /// Compares two MSG, IDL or DTP files and reports the changes of their fields
+    Diff {
+        /// The old version, DTP files are compared in their converted form
+        #[arg(long = "old")]
+        path_to_old_file: String,
+        /// The new version, DTP files are compared in their converted form
+        #[arg(long = "new")]
+        path_to_new_file: String,
+        /// The name of the package the files belong to
+        #[arg(short = 'p', long = "package-name")]
+        package_name: String,
+        /// How static arrays with partial initial values are handled
+        #[arg(long = "array-padding", value_enum, default_value_t = ArrayPaddingArg::Pad)]
+        array_padding: ArrayPaddingArg,
+    },
+    /// Prints as JSON whether the changes between two MSG, IDL or DTP files break existing 4diac applications, fails if they do
+    CheckCompat {
+        /// The old version, DTP files are compared in their converted form
+        #[arg(long = "old")]
+        path_to_old_file: String,
+        /// The new version, DTP files are compared in their converted form
+        #[arg(long = "new")]
+        path_to_new_file: String,
+        /// The name of the package the files belong to
+        #[arg(short = 'p', long = "package-name")]
+        package_name: String,
+        /// How static arrays with partial initial values are handled
+        #[arg(long = "array-padding", value_enum, default_value_t = ArrayPaddingArg::Pad)]
+        array_padding: ArrayPaddingArg,
+    },
+    /// Watches a directory and converts its MSG, IDL, SRV and DTP files and the files referencing them whenever they change
+    Watch {
+        /// The directory to watch
+        #[arg(short = 's', long = "source-directory")]
+        path_to_source_directory: String,
+        /// The directory where the conversion results will be written, it must differ from the watched directory
+        #[arg(short = 'd', long = "destination-directory")]
+        path_to_destination_directory: String,
+        /// The name of the package the files belong to
+        #[arg(short = 'p', long = "package-name")]
+        package_name: String,
+        /// How the package is encoded into the names of the generated types
+        #[arg(long = "type-naming", value_enum, default_value_t = TypeNamingArg::Mangled)]
+        type_naming: TypeNamingArg,
+        /// How static arrays with partial initial values are handled
+        #[arg(long = "array-padding", value_enum, default_value_t = ArrayPaddingArg::Pad)]
+        array_padding: ArrayPaddingArg,
     },
-    /// Print msg file data structure
+    /// Print the data structure of every message in a msg, idl or srv file, one JSON value or YAML document
+    /// per message, i.e. the request and the response of a service
     PrintMsg {
         /// The file to read
         #[arg(short = 'f', long = "file")]
         path_to_msg_file: String,
+        /// The format the data structure is printed in
+        #[arg(long = "format", value_enum, default_value_t = OutputFormatArg::Json)]
+        format: OutputFormatArg,
     },
-    /// Print dtp file data structure
+    /// Print dtp file data structure as a JSON value or YAML document
     PrintDtp {
         /// The file to read
         #[arg(short = 'f', long = "file")]
         path_to_dtp_file: String,
+        /// The format the data structure is printed in
+        #[arg(long = "format", value_enum, default_value_t = OutputFormatArg::Json)]
+        format: OutputFormatArg,
     },
-    /// Use in project root to apply tests
-    Test,
+}
+
+/// How initial values of static arrays with fewer elements than the array are handled
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum ArrayPaddingArg {
+    /// Fill the remaining elements with the default value of the element type, which only elementary types have
+    Pad,
+    /// Fail the conversion
+    Reject,
+}
+
+impl From<ArrayPaddingArg> for ArrayPadding {
+    fn from(value: ArrayPaddingArg) -> Self {
+        match value {
+            ArrayPaddingArg::Pad => ArrayPadding::Pad,
+            ArrayPaddingArg::Reject => ArrayPadding::Reject,
+        }
+    }
+}
+
+/// How the ROS 2 package of an interface is encoded into the name of the converted type
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum TypeNamingArg {
+    /// Prefix the name, e.g. "ROS2_geometrymsgs_msg_Point", for 4diac IDE versions without packages
+    Mangled,
+    /// Declare a package, e.g. "ros2::geometry_msgs::Point", as supported since 4diac IDE 3.0
+    Packaged,
+}
+
+impl From<TypeNamingArg> for TypeNaming {
+    fn from(value: TypeNamingArg) -> Self {
+        match value {
+            TypeNamingArg::Mangled => TypeNaming::Mangled,
+            TypeNamingArg::Packaged => TypeNaming::Packaged,
+        }
+    }
+}
+
+/// How the read data structures are printed
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum OutputFormatArg {
+    Json,
+    Yaml,
+}
+
+impl From<OutputFormatArg> for OutputFormat {
+    fn from(value: OutputFormatArg) -> Self {
+        match value {
+            OutputFormatArg::Json => OutputFormat::Json,
+            OutputFormatArg::Yaml => OutputFormat::Yaml,
+        }
+    }
 }
 
 pub fn run() {
@@ -62,75 +223,116 @@ pub fn run() {
     debug!("Command: {:?}", cli.command);
     let result = match cli.command {
         Command::ConvertToDtp {
-            path_to_msg_file,
+            paths_to_msg_files,
             path_to_destination_directory,
             package_name,
-        } => convert_to_dtp(
-            &path_to_msg_file,
+            generate_function_blocks,
+            generate_forte_code,
+            typelib,
+            author,
+            organization,
+            type_version,
+            type_naming,
+            incremental,
+        } => {
+            let options = DtpOptions {
+                generate_function_blocks,
+                generate_forte_code,
+                author,
+                organization,
+                type_version,
+                type_naming: type_naming.into(),
+                incremental,
+            };
+            if typelib {
+                convert_to_typelib(
+                    &paths_to_msg_files,
+                    &path_to_destination_directory,
+                    &package_name,
+                    &options,
+                )
+            } else {
+                convert_all_to_dtp(
+                    &paths_to_msg_files,
+                    &path_to_destination_directory,
+                    &package_name,
+                    &options,
+                )
+            }
+        }
+        Command::ConvertToMsg {
+            path_to_dtp_file,
+            path_to_destination_directory,
+            package_name,
+            array_padding,
+        } => convert_to_msg(
+            &path_to_dtp_file,
             &path_to_destination_directory,
             &package_name,
+            array_padding.into(),
         ),
-        Command::ConvertToMsg {
+        Command::ConvertToIdl {
             path_to_dtp_file,
             path_to_destination_directory,
             package_name,
-        } => convert_to_msg(
+            array_padding,
+        } => convert_to_idl(
             &path_to_dtp_file,
             &path_to_destination_directory,
             &package_name,
+            array_padding.into(),
         ),
-        Command::PrintMsg { path_to_msg_file } => print_msg(&path_to_msg_file),
-        Command::PrintDtp { path_to_dtp_file } => print_dtp(&path_to_dtp_file),
-        Command::Test => {
-            // hin
-            let _ = convert_to_msg("test/0-dtp/Iec61499Arrayspezifikationen1.dtp", "test/1-msg/", "conversion_tests");
-            let _ = convert_to_msg("test/0-dtp/Iec61499Arrayspezifikationen2.dtp", "test/1-msg/", "conversion_tests");
-            let _ = convert_to_msg("test/0-dtp/Iec61499PrimitiveDatentypen.dtp", "test/1-msg/", "conversion_tests");
-            let _ = convert_to_msg("test/0-dtp/Iec61499Referenzen.dtp", "test/1-msg/", "conversion_tests");
-            let _ = convert_to_msg("test/0-dtp/Iec61499Standardwertliterale1.dtp", "test/1-msg/", "conversion_tests");
-            let _ = convert_to_msg("test/0-dtp/Iec61499Standardwertliterale2.dtp", "test/1-msg/", "conversion_tests");
-            let _ = convert_to_dtp("test/0-msg/Ros2Arrayspezifikationen1.msg", "test/1-dtp/", "conversion_tests");
-            let _ = convert_to_dtp("test/0-msg/Ros2Arrayspezifikationen2.msg", "test/1-dtp/", "conversion_tests");
-            let _ = convert_to_dtp("test/0-msg/Ros2Arrayspezifikationen3.msg", "test/1-dtp/", "conversion_tests");
-            let _ = convert_to_dtp("test/0-msg/Ros2Arrayspezifikationen4.msg", "test/1-dtp/", "conversion_tests");
-            let _ = convert_to_dtp("test/0-msg/Ros2Arrayspezifikationen5.msg", "test/1-dtp/", "conversion_tests");
-            let _ = convert_to_dtp("test/0-msg/Ros2Arrayspezifikationen6.msg", "test/1-dtp/", "conversion_tests");
-            let _ = convert_to_dtp("test/0-msg/Ros2Konstanten.msg", "test/1-dtp/", "conversion_tests");
-            let _ = convert_to_dtp("test/0-msg/Ros2PrimitiveDatentypen.msg", "test/1-dtp/", "conversion_tests");
-            let _ = convert_to_dtp("test/0-msg/Ros2Referenzen.msg", "test/1-dtp/", "conversion_tests");
-            let _ = convert_to_dtp("test/0-msg/Ros2Standardwertliterale1.msg", "test/1-dtp/", "conversion_tests");
-            let _ = convert_to_dtp("test/0-msg/Ros2Standardwertliterale2.msg", "test/1-dtp/", "conversion_tests");
-            let _ = convert_to_dtp("test/0-msg/Ros2Standardwertliterale3.msg", "test/1-dtp/", "conversion_tests");
-            let _ = convert_to_dtp("test/0-msg/Ros2Standardwertliterale4.msg", "test/1-dtp/", "conversion_tests");
-            let _ = convert_to_dtp("test/0-msg/Ros2Standardwertliterale5.msg", "test/1-dtp/", "conversion_tests");
-            let _ = convert_to_dtp("test/0-msg/Ros2Standardwertliterale6.msg", "test/1-dtp/", "conversion_tests");
-            let _ = convert_to_dtp("test/0-msg/Ros2Standardwertliterale7.msg", "test/1-dtp/", "conversion_tests");
-            
-            // zurück
-            let _ = convert_to_dtp("test/1-msg/Iec61499Arrayspezifikationen1.msg", "test/2-dtp/", "conversion_tests");
-            let _ = convert_to_dtp("test/1-msg/Iec61499Arrayspezifikationen2.msg", "test/2-dtp/", "conversion_tests");
-            let _ = convert_to_dtp("test/1-msg/Iec61499PrimitiveDatentypen.msg", "test/2-dtp/", "conversion_tests");
-            let _ = convert_to_dtp("test/1-msg/Iec61499Referenzen.msg", "test/2-dtp/", "conversion_tests");
-            let _ = convert_to_dtp("test/1-msg/Iec61499Standardwertliterale1.msg", "test/2-dtp/", "conversion_tests");
-            let _ = convert_to_dtp("test/1-msg/Iec61499Standardwertliterale2.msg", "test/2-dtp/", "conversion_tests");
-            let _ = convert_to_msg("test/1-dtp/ROS2_conversiontests_msg_Ros2Arrayspezifikationen1.dtp", "test/2-msg/", "conversion_tests");
-            let _ = convert_to_msg("test/1-dtp/ROS2_conversiontests_msg_Ros2Arrayspezifikationen2.dtp", "test/2-msg/", "conversion_tests");
-            let _ = convert_to_msg("test/1-dtp/ROS2_conversiontests_msg_Ros2Arrayspezifikationen3.dtp", "test/2-msg/", "conversion_tests");
-            let _ = convert_to_msg("test/1-dtp/ROS2_conversiontests_msg_Ros2Arrayspezifikationen4.dtp", "test/2-msg/", "conversion_tests");
-            let _ = convert_to_msg("test/1-dtp/ROS2_conversiontests_msg_Ros2Arrayspezifikationen5.dtp", "test/2-msg/", "conversion_tests");
-            let _ = convert_to_msg("test/1-dtp/ROS2_conversiontests_msg_Ros2Arrayspezifikationen6.dtp", "test/2-msg/", "conversion_tests");
-            let _ = convert_to_msg("test/1-dtp/ROS2_conversiontests_msg_Ros2Konstanten.dtp", "test/2-msg/", "conversion_tests");
-            let _ = convert_to_msg("test/1-dtp/ROS2_conversiontests_msg_Ros2PrimitiveDatentypen.dtp", "test/2-msg/", "conversion_tests");
-            let _ = convert_to_msg("test/1-dtp/ROS2_conversiontests_msg_Ros2Referenzen.dtp", "test/2-msg/", "conversion_tests");
-            let _ = convert_to_msg("test/1-dtp/ROS2_conversiontests_msg_Ros2Standardwertliterale1.dtp", "test/2-msg/", "conversion_tests");
-            let _ = convert_to_msg("test/1-dtp/ROS2_conversiontests_msg_Ros2Standardwertliterale2.dtp", "test/2-msg/", "conversion_tests");
-            let _ = convert_to_msg("test/1-dtp/ROS2_conversiontests_msg_Ros2Standardwertliterale3.dtp", "test/2-msg/", "conversion_tests");
-            let _ = convert_to_msg("test/1-dtp/ROS2_conversiontests_msg_Ros2Standardwertliterale4.dtp", "test/2-msg/", "conversion_tests");
-            let _ = convert_to_msg("test/1-dtp/ROS2_conversiontests_msg_Ros2Standardwertliterale5.dtp", "test/2-msg/", "conversion_tests");
-            let _ = convert_to_msg("test/1-dtp/ROS2_conversiontests_msg_Ros2Standardwertliterale6.dtp", "test/2-msg/", "conversion_tests");
-            let _ = convert_to_msg("test/1-dtp/ROS2_conversiontests_msg_Ros2Standardwertliterale7.dtp", "test/2-msg/", "conversion_tests");
-            Ok(())
-        }
+        Command::VerifyRoundtrip {
+            path_to_file,
+            package_name,
+            array_padding,
+        } => verify_roundtrip(&path_to_file, &package_name, array_padding.into()),
+        Command::Diff {
+            path_to_old_file,
+            path_to_new_file,
+            package_name,
+            array_padding,
+        } => diff(
+            &path_to_old_file,
+            &path_to_new_file,
+            &package_name,
+            array_padding.into(),
+        ),
+        Command::CheckCompat {
+            path_to_old_file,
+            path_to_new_file,
+            package_name,
+            array_padding,
+        } => check_compat(
+            &path_to_old_file,
+            &path_to_new_file,
+            &package_name,
+            array_padding.into(),
+        ),
+        Command::Watch {
+            path_to_source_directory,
+            path_to_destination_directory,
+            package_name,
+            type_naming,
+            array_padding,
+        } => watch(
+            &path_to_source_directory,
+            &path_to_destination_directory,
+            &package_name,
+            &DtpOptions {
+                type_naming: type_naming.into(),
+                ..DtpOptions::default()
+            },
+            array_padding.into(),
+        ),
+        Command::PrintMsg {
+            path_to_msg_file,
+            format,
+        } => print_msg(&path_to_msg_file, format.into()),
+        Command::PrintDtp {
+            path_to_dtp_file,
+            format,
+        } => print_dtp(&path_to_dtp_file, format.into()),
     };
 
     if let Err(error) = result {
@@ -0,0 +1,153 @@
+use serde::Serialize;
+
+use crate::business::model_diff::{self, Change};
+use crate::core::msg;
+
+/// Whether the changes between two versions of a message keep existing 4diac applications working
+#[derive(Clone, Debug, Serialize)]
+pub struct Report {
+    compatible: bool,
+    changes: Vec<ClassifiedChange>,
+}
+
+impl Report {
+    pub fn compatible(&self) -> bool {
+        self.compatible
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ClassifiedChange {
+    kind: &'static str,
+    field: String,
+    description: String,
+    breaking: bool,
+}
+
+pub fn check(old: &msg::StructuredType, new: &msg::StructuredType) -> Report {
+    let changes: Vec<ClassifiedChange> = model_diff::diff(old, new)
+        .into_iter()
+        .map(|change| ClassifiedChange {
+            kind: kind(&change),
+            field: field_name(&change).to_string(),
+            description: change.to_string(),
+            breaking: is_breaking(&change),
+        })
+        .collect();
+    Report {
+        compatible: changes.iter().all(|change| !change.breaking),
+        changes,
+    }
+}
+
+fn kind(change: &Change) -> &'static str {
+    match change {
+        Change::Added { .. } => "added",
+        Change::Removed { .. } => "removed",
+        Change::Renamed { .. } => "renamed",
+        Change::Moved { .. } => "moved",
+        Change::FieldTypeChanged { .. } => "field_type_changed",
+        Change::BaseTypeChanged { .. } => "base_type_changed",
+        Change::ArraySizeChanged { .. } => "array_size_changed",
+        Change::InitialValueChanged { .. } => "initial_value_changed",
+    }
+}
+
+fn field_name(change: &Change) -> &str {
+    match change {
+        Change::Renamed { from, .. } => from,
+        Change::Added { name }
+        | Change::Removed { name }
+        | Change::Moved { name, .. }
+        | Change::FieldTypeChanged { name, .. }
+        | Change::BaseTypeChanged { name, .. }
+        | Change::ArraySizeChanged { name, .. }
+        | Change::InitialValueChanged { name, .. } => name,
+    }
+}
+
+// 4diac applications access the elements of a data type by name and only rely on their types,
+// initial values merely apply to newly created instances
+fn is_breaking(change: &Change) -> bool {
+    match change {
+        Change::Added { .. } | Change::Moved { .. } | Change::InitialValueChanged { .. } => false,
+        Change::Removed { .. }
+        | Change::Renamed { .. }
+        | Change::FieldTypeChanged { .. }
+        | Change::BaseTypeChanged { .. } => true,
+        // a different kind of array is converted to a different structure of the data type
+        Change::ArraySizeChanged { from, to, .. } => !matches!(
+            (from, to),
+            (
+                Some(msg::ArraySize::Capacity(_)),
+                Some(msg::ArraySize::Capacity(_))
+            ) | (
+                Some(msg::ArraySize::BoundDynamic(_)),
+                Some(msg::ArraySize::BoundDynamic(_))
+            )
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::business::msg_converter::msg_reader;
+
+    fn check_str(old: &str, new: &str) -> Report {
+        let old = msg_reader::parse("Msg".to_string(), old).unwrap();
+        let new = msg_reader::parse("Msg".to_string(), new).unwrap();
+        check(&old, &new)
+    }
+
+    fn kinds(report: &Report) -> Vec<(&'static str, bool)> {
+        report
+            .changes
+            .iter()
+            .map(|change| (change.kind, change.breaking))
+            .collect()
+    }
+
+    #[test]
+    fn adding_moving_and_changing_initial_values_is_compatible() {
+        let report = check_str("int32 a\nint32 b 1\n", "int32 b 2\nint32 a\nstring c\n");
+        assert!(report.compatible());
+        assert_eq!(
+            kinds(&report),
+            vec![
+                ("moved", false),
+                ("initial_value_changed", false),
+                ("added", false)
+            ]
+        );
+    }
+
+    #[test]
+    fn removing_renaming_and_changing_types_is_breaking() {
+        for (old, new, kind) in [
+            ("int32 a\nint32 b\n", "int32 a\n", "removed"),
+            ("int32 a\nstring b\n", "int32 a\nstring c\n", "renamed"),
+            ("int32 a\n", "int64 a\n", "base_type_changed"),
+            ("int32 A=1\n", "int32 A 1\n", "field_type_changed"),
+        ] {
+            let report = check_str(old, new);
+            assert!(!report.compatible(), "{kind}");
+            assert_eq!(kinds(&report), vec![(kind, true)]);
+        }
+    }
+
+    #[test]
+    fn only_resizing_an_array_of_the_same_kind_is_compatible() {
+        for (old, new, breaking) in [
+            ("int32[2] a\n", "int32[3] a\n", false),
+            ("int32[<=2] a\n", "int32[<=3] a\n", false),
+            ("int32[2] a\n", "int32[<=2] a\n", true),
+            ("int32[] a\n", "int32[<=2] a\n", true),
+            ("int32 a\n", "int32[2] a\n", true),
+        ] {
+            let report = check_str(old, new);
+            assert_eq!(report.compatible(), !breaking, "{old} -> {new}");
+            assert_eq!(kinds(&report), vec![("array_size_changed", breaking)]);
+        }
+    }
+}
@@ -1,53 +1,103 @@
+use std::collections::HashMap;
+
 use crate::business::error::Result;
+use crate::business::naming;
+use crate::business::validation::{self, MAX_PADDED_ARRAY_LENGTH};
 use crate::core::dtp::{
     ANNOTATION_NAME_ROS2_ABSOLUTE_REFERENCE, ANNOTATION_NAME_ROS2_BOUND_DYNAMIC_ARRAY,
-    ANNOTATION_NAME_ROS2_CONSTANT, ANNOTATION_NAME_ROS2_DYNAMIC_ARRAY,
-    ANNOTATION_NAME_ROS2_ELEMENT_COUNTER, ANNOTATION_NAME_ROS2_RELATIVE_REFERENCE,
+    ANNOTATION_NAME_ROS2_CONSTANT, ANNOTATION_NAME_ROS2_DEFAULT, ANNOTATION_NAME_ROS2_DYNAMIC_ARRAY,
+    ANNOTATION_NAME_ROS2_ELEMENT_COUNTER, ANNOTATION_NAME_ROS2_KEY, ANNOTATION_NAME_ROS2_NAME,
+    ANNOTATION_NAME_ROS2_RANGE_MAX, ANNOTATION_NAME_ROS2_RANGE_MIN,
+    ANNOTATION_NAME_ROS2_RELATIVE_REFERENCE, ANNOTATION_NAME_ROS2_UNIT,
+    ANNOTATION_NAME_ROS2_VERBATIM, PACKAGE_NAME_ROS2, PACKAGE_SEPARATOR,
 };
 use crate::core::msg::{
-    ANNOTATION_NAME_IEC61499_DWORD, ANNOTATION_NAME_IEC61499_LWORD,
+    ANNOTATION_NAME_IEC61499_DWORD, ANNOTATION_NAME_IEC61499_LWORD, ANNOTATION_NAME_IEC61499_NAME,
     ANNOTATION_NAME_IEC61499_START_INDEX, ANNOTATION_NAME_IEC61499_WORD,
 };
 use crate::core::{dtp, msg};
 
-pub fn convert(module_name: &str, data_type: &dtp::DataType) -> Result<msg::StructuredType> {
+/// How initial values of static arrays with fewer elements than the array are handled
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ArrayPadding {
+    /// Fill the remaining elements with the default value of the element type, which only
+    /// elementary types have
+    Pad,
+    /// Fail the conversion
+    Reject,
+}
+
+pub fn convert(
+    module_name: &str,
+    data_type: &dtp::DataType,
+    array_padding: ArrayPadding,
+) -> Result<msg::StructuredType> {
     let structured_type = data_type.structured_type();
     let name = convert_data_type_name(module_name, data_type)?;
-    let fields: Vec<msg::Field> = convert_structured_type(module_name, structured_type)?;
+    let fields: Vec<msg::Field> =
+        convert_structured_type(module_name, structured_type, array_padding)?;
+    validation::validate_fields(&fields)?;
     Ok(msg::StructuredType::new(name, fields))
 }
 
-fn convert_data_type_name(module_name: &str, data_type: &dtp::DataType) -> Result<String> {
+pub(crate) fn convert_data_type_name(module_name: &str, data_type: &dtp::DataType) -> Result<String> {
+    Ok(strip_mangled_prefix(module_name, data_type.name()).to_string())
+}
+
+pub(crate) fn strip_mangled_prefix<'a>(module_name: &str, full_name: &'a str) -> &'a str {
     let module_name = module_name
         .replace("_", "")
         .replace(" ", "")
         .replace("-", "");
-    let full_name = data_type.name();
-    Ok(full_name
-        .strip_prefix(&format!("ROS2_{module_name}_msg_"))
+    ["msg", "srv"]
+        .iter()
+        .find_map(|interface_type| {
+            full_name.strip_prefix(&format!("ROS2_{module_name}_{interface_type}_"))
+        })
         .unwrap_or(full_name)
-        .to_string())
 }
 
 fn convert_structured_type(
     module_name: &str,
     structured_type: &dtp::StructuredType,
+    array_padding: ArrayPadding,
 ) -> Result<Vec<msg::Field>> {
+    check_field_name_collisions(structured_type)?;
     let mut fields: Vec<msg::Field> = Vec::new();
     for var_declaration in structured_type.var_declarations() {
         fields.append(&mut convert_var_declaration(
             module_name,
             structured_type,
             var_declaration,
+            array_padding,
         )?)
     }
     Ok(fields)
 }
 
+fn check_field_name_collisions(structured_type: &dtp::StructuredType) -> Result<()> {
+    let mut field_names: HashMap<String, &str> = HashMap::new();
+    for var_declaration in structured_type.var_declarations() {
+        if is_helper(var_declaration) {
+            continue;
+        }
+        let field_name = convert_to_field_name(var_declaration);
+        if let Some(other_name) = field_names.insert(field_name.clone(), var_declaration.name()) {
+            return Err(format!(
+                "\"{other_name}\" and \"{}\" are both mapped to the field name \"{field_name}\"",
+                var_declaration.name()
+            )
+            .into());
+        }
+    }
+    Ok(())
+}
+
 fn convert_var_declaration(
     module_name: &str,
     structured_type: &dtp::StructuredType,
     var_declaration: &dtp::VarDeclaration,
+    array_padding: ArrayPadding,
 ) -> Result<Vec<msg::Field>> {
     if is_helper(var_declaration) {
         return Ok(Vec::new());
@@ -56,10 +106,11 @@ fn convert_var_declaration(
     Ok(vec![msg::Field::new(
         convert_to_field_name(var_declaration),
         convert_to_msg_base_type(module_name, var_declaration),
-        convert_to_msg_array_size(var_declaration),
+        convert_to_msg_array_size(var_declaration)?,
         convert_to_field_type(var_declaration),
-        convert_to_optional_initial_value(structured_type, var_declaration)?,
+        convert_to_optional_initial_value(structured_type, var_declaration, array_padding)?,
         convert_to_msg_comment(var_declaration),
+        convert_to_msg_annotations(structured_type, var_declaration, array_padding)?,
     )])
 }
 
@@ -92,22 +143,53 @@ fn convert_to_msg_base_type(
     }
 }
 
-fn convert_to_msg_array_size(var_declaration: &dtp::VarDeclaration) -> Option<msg::ArraySize> {
-    match var_declaration.array_size() {
-        Some(dtp::ArraySize::Indexation(start, end)) => {
-            Some(msg::ArraySize::Capacity((*end - *start + 1) as u64))
-        }
+fn convert_to_msg_array_size(
+    var_declaration: &dtp::VarDeclaration,
+) -> Result<Option<msg::ArraySize>> {
+    Ok(match var_declaration.array_size() {
+        Some(dtp::ArraySize::Indexation(start, end)) => Some(msg::ArraySize::Capacity(
+            indexation_length(var_declaration, *start, *end)?,
+        )),
         Some(dtp::ArraySize::Capacity(capacity)) => Some(match () {
             _ if is_dynamic_array(var_declaration) => msg::ArraySize::Dynamic,
             _ if is_bound_dynamic_array(var_declaration) => msg::ArraySize::BoundDynamic(*capacity),
             _ => msg::ArraySize::Capacity(*capacity),
         }),
         _ => None,
-    }
+    })
+}
+
+fn indexation_length(var_declaration: &dtp::VarDeclaration, start: i64, end: i64) -> Result<u64> {
+    end.checked_sub(start)
+        .and_then(|difference| u64::try_from(difference).ok())
+        .and_then(|difference| difference.checked_add(1))
+        .ok_or_else(|| {
+            format!(
+                "The indexation {start}..{end} of \"{}\" is not a valid array size",
+                var_declaration.name()
+            )
+            .into()
+        })
 }
 
 fn convert_to_field_name(var_declaration: &dtp::VarDeclaration) -> String {
-    var_declaration.name().to_string()
+    if let Some(ros2_name) = get_ros2_name(var_declaration) {
+        return ros2_name;
+    }
+    naming::escape_ros2_keyword(if is_constant(var_declaration) {
+        naming::to_ros2_constant_name(var_declaration.name())
+    } else {
+        naming::to_ros2_field_name(var_declaration.name())
+    })
+}
+
+fn get_ros2_name(var_declaration: &dtp::VarDeclaration) -> Option<String> {
+    filter_attributes(var_declaration, ANNOTATION_NAME_ROS2_NAME).find_map(|attribute| {
+        match attribute.value() {
+            dtp::InitialValue::STRING(name) => Some(convert_string_representation(name)),
+            _ => None,
+        }
+    })
 }
 
 fn convert_to_field_type(var_declaration: &dtp::VarDeclaration) -> msg::FieldType {
@@ -121,15 +203,26 @@ fn convert_to_field_type(var_declaration: &dtp::VarDeclaration) -> msg::FieldTyp
 fn convert_to_optional_initial_value(
     structured_type: &dtp::StructuredType,
     var_declaration: &dtp::VarDeclaration,
+    array_padding: ArrayPadding,
 ) -> Result<Option<msg::InitialValue>> {
     var_declaration
         .initial_value()
-        .map(|initial_value| convert_initial_value(structured_type, var_declaration, initial_value))
+        .map(|initial_value| {
+            convert_initial_value(structured_type, var_declaration, initial_value, array_padding)
+        })
         .transpose()
 }
 
 fn convert_to_msg_comment(var_declaration: &dtp::VarDeclaration) -> Option<String> {
     let mut annotations: Vec<String> = Vec::new();
+    if get_ros2_name(var_declaration).is_none()
+        && convert_to_field_name(var_declaration) != var_declaration.name()
+    {
+        annotations.push(format!(
+            "@{ANNOTATION_NAME_IEC61499_NAME}({})",
+            var_declaration.name()
+        ));
+    }
     annotations.append(&mut match *var_declaration.base_type() {
         dtp::BaseType::WORD => vec![format!("@{ANNOTATION_NAME_IEC61499_WORD}")],
         dtp::BaseType::DWORD => vec![format!("@{ANNOTATION_NAME_IEC61499_DWORD}")],
@@ -152,10 +245,54 @@ fn convert_to_msg_comment(var_declaration: &dtp::VarDeclaration) -> Option<Strin
     }
 }
 
+fn convert_to_msg_annotations(
+    structured_type: &dtp::StructuredType,
+    var_declaration: &dtp::VarDeclaration,
+    array_padding: ArrayPadding,
+) -> Result<Vec<msg::Annotation>> {
+    let mut annotations = Vec::new();
+    if filter_attributes(var_declaration, ANNOTATION_NAME_ROS2_KEY).next().is_some() {
+        annotations.push(msg::Annotation::Key);
+    }
+    if let Some(dtp::InitialValue::STRING(unit)) =
+        filter_attributes(var_declaration, ANNOTATION_NAME_ROS2_UNIT)
+            .next()
+            .map(|attribute| attribute.value())
+    {
+        annotations.push(msg::Annotation::Unit(convert_string_representation(unit)));
+    }
+    let convert_attribute_value = |name: &'static str| {
+        filter_attributes(var_declaration, name)
+            .next()
+            .map(|attribute| {
+                convert_initial_value(structured_type, var_declaration, attribute.value(), array_padding)
+            })
+            .transpose()
+    };
+    let min = convert_attribute_value(ANNOTATION_NAME_ROS2_RANGE_MIN)?;
+    let max = convert_attribute_value(ANNOTATION_NAME_ROS2_RANGE_MAX)?;
+    if min.is_some() || max.is_some() {
+        annotations.push(msg::Annotation::Range { min, max });
+    }
+    if let Some(default) = convert_attribute_value(ANNOTATION_NAME_ROS2_DEFAULT)? {
+        annotations.push(msg::Annotation::Default(default));
+    }
+    for attribute in filter_attributes(var_declaration, ANNOTATION_NAME_ROS2_VERBATIM) {
+        if let dtp::InitialValue::STRING(text) = attribute.value() {
+            annotations.push(msg::Annotation::Verbatim {
+                language: attribute.comment().cloned(),
+                text: convert_string_representation(text),
+            });
+        }
+    }
+    Ok(annotations)
+}
+
 fn convert_initial_value(
     structured_type: &dtp::StructuredType,
     var_declaration: &dtp::VarDeclaration,
     initial_value: &dtp::InitialValue,
+    array_padding: ArrayPadding,
 ) -> Result<msg::InitialValue> {
     let result = match initial_value {
         dtp::InitialValue::BOOL(bool_representation) => {
@@ -213,15 +350,26 @@ fn convert_initial_value(
             msg::InitialValue::Wstring(convert_wstring_representation(wstring_representation))
         }
         dtp::InitialValue::Array(v) => {
-            let slice = if is_dynamic_array(var_declaration) {
-                &v[..convert_default_dynamic_array_count(structured_type, var_declaration)?
-                    as usize]
+            // bound dynamic arrays are padded to their bound like dynamic arrays, so only the
+            // elements up to the element counter belong to the initial value
+            let values = if is_dynamic_array(var_declaration)
+                || is_bound_dynamic_array(var_declaration)
+            {
+                let count = convert_default_dynamic_array_count(structured_type, var_declaration)?;
+                v.get(..count as usize)
+                    .ok_or(format!(
+                        "The element counter of \"{}\" exceeds its initial value",
+                        var_declaration.name()
+                    ))?
+                    .to_vec()
+            } else if let Some(length) = static_array_length(var_declaration)? {
+                pad_static_array(var_declaration, v, length, array_padding)?
             } else {
-                v
+                v.to_vec()
             };
-            slice
+            values
                 .iter()
-                .map(|v| convert_initial_value(structured_type, var_declaration, v))
+                .map(|v| convert_initial_value(structured_type, var_declaration, v, array_padding))
                 .collect::<Result<Vec<_>>>()
                 .map(msg::InitialValue::Array)?
         }
@@ -229,11 +377,160 @@ fn convert_initial_value(
     Ok(result)
 }
 
+fn static_array_length(var_declaration: &dtp::VarDeclaration) -> Result<Option<u64>> {
+    Ok(match var_declaration.array_size() {
+        Some(dtp::ArraySize::Indexation(start, end)) => {
+            Some(indexation_length(var_declaration, *start, *end)?)
+        }
+        Some(dtp::ArraySize::Capacity(capacity))
+            if !is_dynamic_array(var_declaration) && !is_bound_dynamic_array(var_declaration) =>
+        {
+            Some(*capacity)
+        }
+        _ => None,
+    })
+}
+
+fn pad_static_array(
+    var_declaration: &dtp::VarDeclaration,
+    values: &[dtp::InitialValue],
+    length: u64,
+    array_padding: ArrayPadding,
+) -> Result<Vec<dtp::InitialValue>> {
+    let count = values.len() as u64;
+    if count > length {
+        return Err(format!(
+            "The initial value of \"{}\" has {count} elements, but the array only holds {length}",
+            var_declaration.name()
+        )
+        .into());
+    }
+    if count == length {
+        return Ok(values.to_vec());
+    }
+    match array_padding {
+        ArrayPadding::Reject => Err(format!(
+            "The initial value of \"{}\" only initializes {count} of {length} array elements",
+            var_declaration.name()
+        )
+        .into()),
+        ArrayPadding::Pad if length > MAX_PADDED_ARRAY_LENGTH => Err(format!(
+            "The initial value of \"{}\" cannot be padded to {length} array elements, \
+            at most {MAX_PADDED_ARRAY_LENGTH} are supported",
+            var_declaration.name()
+        )
+        .into()),
+        ArrayPadding::Pad => {
+            let filler = create_filler_initial_value(var_declaration, values.first())?;
+            let mut padded = values.to_vec();
+            padded.resize(length as usize, filler);
+            Ok(padded)
+        }
+    }
+}
+
+pub(crate) fn create_filler_initial_value(
+    var_declaration: &dtp::VarDeclaration,
+    sample_initial_value: Option<&dtp::InitialValue>,
+) -> Result<dtp::InitialValue> {
+    let int_representation = || create_default_int_representation(sample_initial_value);
+    Ok(match var_declaration.base_type() {
+        dtp::BaseType::BOOL => {
+            dtp::InitialValue::BOOL(create_default_bool_representation(sample_initial_value))
+        }
+        dtp::BaseType::BYTE => dtp::InitialValue::BYTE(int_representation()),
+        dtp::BaseType::WORD => dtp::InitialValue::WORD(int_representation()),
+        dtp::BaseType::DWORD => dtp::InitialValue::DWORD(int_representation()),
+        dtp::BaseType::LWORD => dtp::InitialValue::LWORD(int_representation()),
+        dtp::BaseType::SINT => dtp::InitialValue::SINT(int_representation()),
+        dtp::BaseType::INT => dtp::InitialValue::INT(int_representation()),
+        dtp::BaseType::DINT => dtp::InitialValue::DINT(int_representation()),
+        dtp::BaseType::LINT => dtp::InitialValue::LINT(int_representation()),
+        dtp::BaseType::USINT => dtp::InitialValue::USINT(int_representation()),
+        dtp::BaseType::UINT => dtp::InitialValue::UINT(int_representation()),
+        dtp::BaseType::UDINT => dtp::InitialValue::UDINT(int_representation()),
+        dtp::BaseType::ULINT => dtp::InitialValue::ULINT(int_representation()),
+        dtp::BaseType::REAL => dtp::InitialValue::REAL(0f32),
+        dtp::BaseType::LREAL => dtp::InitialValue::LREAL(0f64),
+        dtp::BaseType::CHAR => dtp::InitialValue::CHAR(dtp::CharRepresentation::Hexadecimal(0)),
+        dtp::BaseType::STRING(_) => dtp::InitialValue::STRING(Vec::new()),
+        dtp::BaseType::WSTRING(_) => dtp::InitialValue::WSTRING(Vec::new()),
+        dtp::BaseType::Custom(type_name) => {
+            return Err(format!(
+                "No default value can be derived for elements of type \"{type_name}\" of \"{}\"",
+                var_declaration.name()
+            )
+            .into())
+        }
+    })
+}
+
+fn create_default_bool_representation(
+    sample_initial_value: Option<&dtp::InitialValue>,
+) -> dtp::BoolRepresentation {
+    match sample_initial_value {
+        Some(dtp::InitialValue::BOOL(dtp::BoolRepresentation::Binary(_))) => {
+            dtp::BoolRepresentation::Binary(false)
+        }
+        _ => dtp::BoolRepresentation::String(false),
+    }
+}
+
+fn create_default_int_representation(
+    sample_initial_value: Option<&dtp::InitialValue>,
+) -> dtp::IntRepresentation {
+    match sample_initial_value {
+        Some(
+            dtp::InitialValue::BYTE(reference)
+            | dtp::InitialValue::WORD(reference)
+            | dtp::InitialValue::DWORD(reference)
+            | dtp::InitialValue::LWORD(reference)
+            | dtp::InitialValue::SINT(reference)
+            | dtp::InitialValue::INT(reference)
+            | dtp::InitialValue::DINT(reference)
+            | dtp::InitialValue::LINT(reference)
+            | dtp::InitialValue::USINT(reference)
+            | dtp::InitialValue::UINT(reference)
+            | dtp::InitialValue::UDINT(reference)
+            | dtp::InitialValue::ULINT(reference),
+        ) => match reference {
+            dtp::IntRepresentation::SignedDecimal(_) => dtp::IntRepresentation::SignedDecimal(0),
+            dtp::IntRepresentation::UnsignedDecimal(_) => {
+                dtp::IntRepresentation::UnsignedDecimal(0)
+            }
+            dtp::IntRepresentation::Binary(_) => dtp::IntRepresentation::Binary(0),
+            dtp::IntRepresentation::Octal(_) => dtp::IntRepresentation::Octal(0),
+            dtp::IntRepresentation::Heaxdecimal(_) => dtp::IntRepresentation::Heaxdecimal(0),
+        },
+        _ => dtp::IntRepresentation::UnsignedDecimal(0),
+    }
+}
+
 fn convert_reference(
     module_name: &str,
     var_declaration: &dtp::VarDeclaration,
-    dtp_reference_string: &str,
+    type_name: &dtp::TypeName,
 ) -> msg::Reference {
+    // qualified names carry the ROS 2 package, e.g. "ros2::geometry_msgs::Point"
+    let ros2_package = type_name.package().and_then(|package| {
+        package
+            .strip_prefix(PACKAGE_NAME_ROS2)
+            .and_then(|package| package.strip_prefix(PACKAGE_SEPARATOR))
+    });
+    // a type of another package can only be referenced absolutely, whatever the attributes say
+    if let Some(package) = ros2_package {
+        return if is_absolute_reference(var_declaration) || package != module_name {
+            msg::Reference::Absolute {
+                package: package.to_string(),
+                file: type_name.name().to_string(),
+            }
+        } else {
+            msg::Reference::Relative {
+                file: type_name.name().to_string(),
+            }
+        };
+    }
+    let dtp_reference_string = type_name.name();
     let reference_parts: Vec<&str> = dtp_reference_string.split("_").collect();
     if is_absolute_reference(var_declaration) && reference_parts.len() == 4 {
         msg::Reference::Absolute {
@@ -246,7 +543,7 @@ fn convert_reference(
         }
     } else {
         msg::Reference::Relative {
-            file: dtp_reference_string.to_string(),
+            file: strip_mangled_prefix(module_name, dtp_reference_string).to_string(),
         }
     }
 }
@@ -278,8 +575,9 @@ fn convert_char_representation(
     char_representation: &dtp::CharRepresentation,
 ) -> msg::IntRepresentation {
     match char_representation {
-        dtp::CharRepresentation::Char(char) | dtp::CharRepresentation::Hexadecimal(char) => {
-            msg::IntRepresentation::Hexadecimal(*char as u64)
+        dtp::CharRepresentation::Char(char) => msg::IntRepresentation::Hexadecimal(*char as u64),
+        dtp::CharRepresentation::Hexadecimal(byte) => {
+            msg::IntRepresentation::Hexadecimal(u64::from(*byte))
         }
     }
 }
@@ -300,7 +598,8 @@ fn convert_wstring_representation(wstring_representation: &[dtp::WcharRepresenta
 
 fn char_representation_to_char(char_representation: &dtp::CharRepresentation) -> char {
     match char_representation {
-        dtp::CharRepresentation::Char(char) | dtp::CharRepresentation::Hexadecimal(char) => *char,
+        dtp::CharRepresentation::Char(char) => *char,
+        dtp::CharRepresentation::Hexadecimal(byte) => char::from(*byte),
     }
 }
 
@@ -328,7 +627,7 @@ fn is_bound_dynamic_array(var_declaration: &dtp::VarDeclaration) -> bool {
         .is_some()
 }
 
-fn is_element_counter_of(main: &dtp::VarDeclaration, helper: &dtp::VarDeclaration) -> bool {
+pub(crate) fn is_element_counter_of(main: &dtp::VarDeclaration, helper: &dtp::VarDeclaration) -> bool {
     filter_element_counter(helper).any(|attribute| {
         matches!(
             attribute.value(),
@@ -390,7 +689,7 @@ fn convert_default_dynamic_array_count(
         .ok_or("No default element count found")?
 }
 
-fn array_bound_from_intial_value(initial_value: &dtp::InitialValue) -> Result<u64> {
+pub(crate) fn array_bound_from_intial_value(initial_value: &dtp::InitialValue) -> Result<u64> {
     match initial_value {
         dtp::InitialValue::ULINT(dtp::IntRepresentation::SignedDecimal(i64)) if 0 < *i64 => {
             Ok(*i64 as u64)
@@ -402,3 +701,123 @@ fn array_bound_from_intial_value(initial_value: &dtp::InitialValue) -> Result<u6
         _ => Err("A valid array bound is expected".into()),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::business::dtp_converter::dtp_reader;
+
+    fn convert_str(
+        var_declarations: &str,
+        array_padding: ArrayPadding,
+    ) -> Result<msg::StructuredType> {
+        let data_type = dtp_reader::parse(
+            format!(
+                r#"<?xml version="1.0" encoding="UTF-8"?>
+                <DataType Name="Sample">
+                    <StructuredType>
+                        {var_declarations}
+                    </StructuredType>
+                </DataType>"#
+            )
+            .as_bytes(),
+        )?;
+        convert("pkg", &data_type, array_padding)
+    }
+
+    fn initial_value(structured_type: &msg::StructuredType) -> Option<&msg::InitialValue> {
+        structured_type.fields()[0].initial_value()
+    }
+
+    fn int32_array(values: &[u64]) -> msg::InitialValue {
+        msg::InitialValue::Array(
+            values
+                .iter()
+                .map(|value| {
+                    msg::InitialValue::Int32(msg::IntRepresentation::UnsignedDecimal(*value))
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn qualified_types_of_other_packages_are_referenced_absolutely() {
+        let structured_type = convert_str(
+            r#"<VarDeclaration Name="p" Type="ros2::geometry_msgs::Point"/>
+            <VarDeclaration Name="q" Type="ros2::pkg::Point"/>"#,
+            ArrayPadding::Reject,
+        )
+        .unwrap();
+        let base_types: Vec<&msg::BaseType> = structured_type
+            .fields()
+            .iter()
+            .map(msg::Field::base_type)
+            .collect();
+        assert_eq!(
+            base_types,
+            vec![
+                &msg::BaseType::Custom(msg::Reference::Absolute {
+                    package: "geometry_msgs".to_string(),
+                    file: "Point".to_string()
+                }),
+                &msg::BaseType::Custom(msg::Reference::Relative {
+                    file: "Point".to_string()
+                })
+            ]
+        );
+    }
+
+    #[test]
+    fn pad_fills_partial_static_arrays_with_default_values() {
+        let structured_type = convert_str(
+            r#"<VarDeclaration Name="v" Type="DINT" ArraySize="4" InitialValue="[1, 2]"/>"#,
+            ArrayPadding::Pad,
+        )
+        .unwrap();
+        assert_eq!(initial_value(&structured_type), Some(&int32_array(&[1, 2, 0, 0])));
+
+        let structured_type = convert_str(
+            r#"<VarDeclaration Name="v" Type="DINT" ArraySize="1..3" InitialValue="[1]"/>"#,
+            ArrayPadding::Pad,
+        )
+        .unwrap();
+        assert_eq!(initial_value(&structured_type), Some(&int32_array(&[1, 0, 0])));
+    }
+
+    #[test]
+    fn reject_fails_on_partial_static_arrays() {
+        assert!(convert_str(
+            r#"<VarDeclaration Name="v" Type="DINT" ArraySize="4" InitialValue="[1, 2]"/>"#,
+            ArrayPadding::Reject,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn complete_static_arrays_are_neither_padded_nor_rejected() {
+        for array_padding in [ArrayPadding::Pad, ArrayPadding::Reject] {
+            let structured_type = convert_str(
+                r#"<VarDeclaration Name="v" Type="DINT" ArraySize="2" InitialValue="[1, 2]"/>"#,
+                array_padding,
+            )
+            .unwrap();
+            assert_eq!(initial_value(&structured_type), Some(&int32_array(&[1, 2])));
+        }
+    }
+
+    #[test]
+    fn initial_values_exceeding_the_array_and_huge_arrays_are_never_padded() {
+        for array_padding in [ArrayPadding::Pad, ArrayPadding::Reject] {
+            assert!(convert_str(
+                r#"<VarDeclaration Name="v" Type="DINT" ArraySize="1" InitialValue="[1, 2]"/>"#,
+                array_padding,
+            )
+            .is_err());
+        }
+        assert!(convert_str(
+            r#"<VarDeclaration Name="v" Type="DINT" ArraySize="100000" InitialValue="[1]"/>"#,
+            ArrayPadding::Pad,
+        )
+        .is_err());
+    }
+}
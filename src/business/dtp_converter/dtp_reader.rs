@@ -1,10 +1,10 @@
 use log::info;
 use nom::branch::alt;
-use nom::bytes::complete::{is_a, tag, take_till1};
+use nom::bytes::complete::{is_a, tag, take_till1, take_while_m_n};
 use nom::character::complete::{digit1, hex_digit1, none_of, oct_digit1, one_of};
-use nom::combinator::{map, map_res, opt, recognize};
-use nom::multi::{many0, many1, many_m_n};
-use nom::sequence::{delimited, preceded, tuple};
+use nom::combinator::{all_consuming, eof, map, map_res, opt, recognize};
+use nom::multi::{many0, many1};
+use nom::sequence::{delimited, preceded, terminated, tuple};
 use nom::{Finish, IResult};
 use std::num::ParseIntError;
 use xmltree::{Element, XMLNode};
@@ -15,17 +15,84 @@ use crate::core::dtp::*;
 pub fn read(path_to_file: &str) -> Result<DataType> {
     info!("Start reading file {:?}", path_to_file);
     let file = std::fs::File::open(path_to_file)?;
-    let data_type = parse_data_type(file)?;
+    let data_type = parse(file)?;
     info!("Finished reading file {:?}", path_to_file);
     Ok(data_type)
 }
 
-fn parse_data_type(file: std::fs::File) -> Result<DataType> {
-    let data_type_element = Element::parse(file)?;
+pub fn parse(source: impl std::io::Read) -> Result<DataType> {
+    let data_type_element = Element::parse(source)?;
     let name = parse_name(&data_type_element)?;
     let comment = parse_comment(&data_type_element);
+    let identification = parse_identification(&data_type_element);
+    let version_infos = parse_version_infos(&data_type_element);
+    let compiler_info = parse_compiler_info(&data_type_element);
     let structured_type = parse_structured_type(&data_type_element)?;
-    Ok(DataType::new(name, comment, structured_type))
+    Ok(DataType::new(
+        name,
+        comment,
+        identification,
+        version_infos,
+        compiler_info,
+        structured_type,
+    ))
+}
+
+fn parse_identification(element: &Element) -> Option<Identification> {
+    get_filtered_children(element, |child| child.name == XML_TAG_IDENTIFICATION)
+        .into_iter()
+        .next()
+        .map(|identification_element| {
+            Identification::new(
+                parse_optional_attribute(identification_element, XML_ATTRIBUTE_STANDARD),
+                parse_optional_attribute(identification_element, XML_ATTRIBUTE_CLASSIFICATION),
+                parse_optional_attribute(identification_element, XML_ATTRIBUTE_APPLICATION_DOMAIN),
+                parse_optional_attribute(identification_element, XML_ATTRIBUTE_FUNCTION),
+                parse_optional_attribute(identification_element, XML_ATTRIBUTE_TYPE),
+                parse_optional_attribute(identification_element, XML_ATTRIBUTE_DESCRIPTION),
+            )
+        })
+}
+
+fn parse_version_infos(element: &Element) -> Vec<VersionInfo> {
+    get_filtered_children(element, |child| child.name == XML_TAG_VERSION_INFO)
+        .into_iter()
+        .map(|version_info_element| {
+            VersionInfo::new(
+                parse_optional_attribute(version_info_element, XML_ATTRIBUTE_ORGANIZATION),
+                parse_optional_attribute(version_info_element, XML_ATTRIBUTE_VERSION),
+                parse_optional_attribute(version_info_element, XML_ATTRIBUTE_AUTHOR),
+                parse_optional_attribute(version_info_element, XML_ATTRIBUTE_DATE),
+                parse_optional_attribute(version_info_element, XML_ATTRIBUTE_REMARKS),
+            )
+        })
+        .collect()
+}
+
+fn parse_compiler_info(element: &Element) -> Option<CompilerInfo> {
+    get_filtered_children(element, |child| child.name == XML_TAG_COMPILER_INFO)
+        .into_iter()
+        .next()
+        .map(|compiler_info_element| {
+            let compilers =
+                get_filtered_children(compiler_info_element, |child| child.name == XML_TAG_COMPILER)
+                    .into_iter()
+                    .map(|compiler_element| {
+                        Compiler::new(
+                            parse_optional_attribute(compiler_element, XML_ATTRIBUTE_LANGUAGE),
+                            parse_optional_attribute(compiler_element, XML_ATTRIBUTE_VENDOR),
+                            parse_optional_attribute(compiler_element, XML_ATTRIBUTE_PRODUCT),
+                            parse_optional_attribute(compiler_element, XML_ATTRIBUTE_VERSION),
+                        )
+                    })
+                    .collect();
+            CompilerInfo::new(
+                parse_optional_attribute(compiler_info_element, XML_ATTRIBUTE_PACKAGE_NAME),
+                parse_optional_attribute(compiler_info_element, XML_ATTRIBUTE_HEADER),
+                parse_optional_attribute(compiler_info_element, XML_ATTRIBUTE_CLASSDEF),
+                compilers,
+            )
+        })
 }
 
 fn parse_structured_type(element: &Element) -> Result<StructuredType> {
@@ -108,6 +175,19 @@ fn parse_attribute(attribute_element: &Element) -> Result<Attribute> {
 
 fn parse_base_type(string: &str) -> Result<BaseType> {
     Ok(alt((
+        // custom types may start like an elementary type, e.g. "INTERVAL"
+        terminated(parse_elementary_type, eof),
+        map(take_till1(|c| c == '"'), |custom_type: &str| {
+            BaseType::Custom(TypeName::parse(custom_type))
+        }),
+    ))(string)
+    .map_err(|e: nom::Err<nom::error::Error<&str>>| e.to_owned())
+    .finish()?
+    .1)
+}
+
+fn parse_elementary_type(input: &str) -> IResult<&str, BaseType> {
+    alt((
         map(tag("BOOL"), |_| BaseType::BOOL),
         map(tag("BYTE"), |_| BaseType::BYTE),
         map(tag("WORD"), |_| BaseType::WORD),
@@ -124,25 +204,25 @@ fn parse_base_type(string: &str) -> Result<BaseType> {
         map(tag("REAL"), |_| BaseType::REAL),
         map(tag("LREAL"), |_| BaseType::LREAL),
         map(tag("CHAR"), |_| BaseType::CHAR),
-        map(
+        map_res(
             tuple((tag("STRING"), opt(delimited(tag("["), digit1, tag("]"))))),
             |(_, optional_bound): (&str, Option<&str>)| {
-                BaseType::STRING(optional_bound.map(|digits| digits.parse().unwrap()))
+                optional_bound
+                    .map(str::parse)
+                    .transpose()
+                    .map(BaseType::STRING)
             },
         ),
-        map(
+        map_res(
             tuple((tag("WSTRING"), opt(delimited(tag("["), digit1, tag("]"))))),
             |(_, optional_bound): (&str, Option<&str>)| {
-                BaseType::WSTRING(optional_bound.map(|digits| digits.parse().unwrap()))
+                optional_bound
+                    .map(str::parse)
+                    .transpose()
+                    .map(BaseType::WSTRING)
             },
         ),
-        map(take_till1(|c| c == '"'), |custom_type: &str| {
-            BaseType::Custom(custom_type.to_string())
-        }),
-    ))(string)
-    .map_err(|e: nom::Err<nom::error::Error<&str>>| e.to_owned())
-    .finish()?
-    .1)
+    ))(input)
 }
 
 fn parse_array_size(input: &str) -> Result<ArraySize> {
@@ -175,11 +255,11 @@ fn parse_initial_value<'a>(
     if array_size.is_some() {
         Box::new(move |str| {
             let trimmmed = str.trim();
-            if !(trimmmed.starts_with('[') && trimmmed.ends_with(']')) {
+            if !(trimmmed.len() >= 2 && trimmmed.starts_with('[') && trimmmed.ends_with(']')) {
                 return Err("An array must use '[]'".into());
             }
-            let values = trimmmed[1..trimmmed.len() - 1]
-                .split(',')
+            let values = split_array_elements(&trimmmed[1..trimmmed.len() - 1])
+                .into_iter()
                 .map(|value| parse_initial_value(base_type, &None)(value.trim()))
                 .collect::<Result<Vec<_>>>()?;
             Ok(InitialValue::Array(values))
@@ -240,11 +320,40 @@ fn parse_initial_value<'a>(
             BaseType::WSTRING(_) => {
                 Box::new(|input| parse_wstring_representation(input).map(InitialValue::WSTRING))
             }
-            BaseType::Custom(_) => unreachable!(),
+            BaseType::Custom(_) => {
+                Box::new(|_| Err("Custom types cannot have an initial value".into()))
+            }
         }
     }
 }
 
+// Splits at commas which are not part of a (possibly escaped) string literal
+fn split_array_elements(input: &str) -> Vec<&str> {
+    if input.trim().is_empty() {
+        return Vec::new();
+    }
+    let mut elements = Vec::new();
+    let mut quote: Option<char> = None;
+    let mut is_masked = false;
+    let mut start = 0;
+    for (i, c) in input.char_indices() {
+        match quote {
+            Some(_) if is_masked => is_masked = false,
+            Some(_) if c == '$' => is_masked = true,
+            Some(delimiter) if c == delimiter => quote = None,
+            Some(_) => {}
+            None if c == '\'' || c == '"' => quote = Some(c),
+            None if c == ',' => {
+                elements.push(&input[start..i]);
+                start = i + 1;
+            }
+            None => {}
+        }
+    }
+    elements.push(&input[start..]);
+    elements
+}
+
 fn parse_name(element: &Element) -> Result<String> {
     Ok(element
         .attributes
@@ -257,10 +366,14 @@ fn parse_name(element: &Element) -> Result<String> {
 }
 
 fn parse_comment(element: &Element) -> Option<String> {
+    parse_optional_attribute(element, XML_ATTRIBUTE_COMMENT)
+}
+
+fn parse_optional_attribute(element: &Element, attribute_name: &str) -> Option<String> {
     element
         .attributes
-        .get_key_value(XML_ATTRIBUTE_COMMENT)
-        .map(|comment| comment.1.clone())
+        .get_key_value(attribute_name)
+        .map(|key_value| key_value.1.clone())
 }
 
 fn get_filtered_children(parent: &Element, filter_fn: fn(&Element) -> bool) -> Vec<&Element> {
@@ -311,34 +424,40 @@ fn parse_lreal_representation(input: &str) -> Result<f64> {
 }
 
 fn parse_char_representation(input: &str) -> Result<CharRepresentation> {
-    if !(input.starts_with("'") && input.ends_with("'")) {
+    if !(input.len() >= 2 && input.starts_with("'") && input.ends_with("'")) {
         return Err("A char literal is expected to be delimited with single quotes (')".into());
     }
     let actual_value = &input[1..input.len() - 1];
 
-    Ok(char_literal_parser(actual_value)
+    let char_representation = all_consuming(char_literal_parser)(actual_value)
         .map_err(|e| e.to_owned())
         .finish()?
-        .1)
+        .1;
+    match char_representation {
+        CharRepresentation::Char(char) if u32::from(char) > 0xFF => {
+            Err(format!("A CHAR holds a single byte, which {char:?} exceeds").into())
+        }
+        _ => Ok(char_representation),
+    }
 }
 
 fn parse_string_representation(input: &str) -> Result<Vec<CharRepresentation>> {
-    if !(input.starts_with("'") && input.ends_with("'")) {
+    if !(input.len() >= 2 && input.starts_with("'") && input.ends_with("'")) {
         return Err("InitialValue of STRING must be delimited with ''".into());
     }
     let actual_value = &input[1..input.len() - 1];
-    Ok(many0(char_literal_parser)(actual_value)
+    Ok(all_consuming(many0(char_literal_parser))(actual_value)
         .map_err(|e| e.to_owned())
         .finish()?
         .1)
 }
 
 fn parse_wstring_representation(input: &str) -> Result<Vec<WcharRepresentation>> {
-    if !(input.starts_with("\"") && input.ends_with("\"")) {
+    if !(input.len() >= 2 && input.starts_with("\"") && input.ends_with("\"")) {
         return Err("InitialValue of WSTRING must be delimited with &quot;&quot;".into());
     }
     let actual_value = &input[1..input.len() - 1];
-    Ok(many0(wchar_literal_parser)(actual_value)
+    Ok(all_consuming(many0(wchar_literal_parser))(actual_value)
         .map_err(|e| e.to_owned())
         .finish()?
         .1)
@@ -356,26 +475,26 @@ fn wchar_literal_parser(input: &str) -> IResult<&str, WcharRepresentation> {
     alt((
         hexadecimal_wchar_literal_parser,
         map(masked_char_parser, WcharRepresentation::Wchar),
-        map(char_parser, WcharRepresentation::Wchar),
+        map(wchar_parser, WcharRepresentation::Wchar),
     ))(input)
 }
 
 fn hexadecimal_char_literal_parser(input: &str) -> IResult<&str, CharRepresentation> {
     map_res(
-        preceded(tag("$"), recognize(many_m_n(1, 1, hex_digit1))),
+        preceded(tag("$"), take_while_m_n(2, 2, |c: char| c.is_ascii_hexdigit())),
         |str| {
             let u8 = u8::from_str_radix(str, 16).map_err(|_| {
                 "A hexadecimal char literal is expected \
                 to contain a valid 2-digit hexadecimal number"
             })?;
-            Ok::<CharRepresentation, Error>(CharRepresentation::Hexadecimal(u8 as char))
+            Ok::<CharRepresentation, Error>(CharRepresentation::Hexadecimal(u8))
         },
     )(input)
 }
 
 fn hexadecimal_wchar_literal_parser(input: &str) -> IResult<&str, WcharRepresentation> {
     map_res(
-        preceded(tag("$"), recognize(many_m_n(4, 4, hex_digit1))),
+        preceded(tag("$"), take_while_m_n(4, 4, |c: char| c.is_ascii_hexdigit())),
         |str| {
             let u16 = u16::from_str_radix(str, 16).map_err(|_| {
                 "A hexadecimal wchar literal is expected \
@@ -391,14 +510,14 @@ fn hexadecimal_wchar_literal_parser(input: &str) -> IResult<&str, WcharRepresent
 }
 
 fn masked_char_parser(input: &str) -> IResult<&str, char> {
-    map_res(
+    map(
         preceded(tag("$"), one_of("LNPTRlnptr$'\"")),
         |char| match char {
-            '$' | '"' | '\'' => Ok::<char, Error>(char),
-            _ => {
-                // TODO: Add support for special characters (=LNPTRlnptr)
-                Err("Unsupported masked char literal".into())
-            }
+            'L' | 'l' | 'N' | 'n' => '\n',
+            'P' | 'p' => '\x0C',
+            'R' | 'r' => '\r',
+            'T' | 't' => '\t',
+            _ => char,
         },
     )(input)
 }
@@ -407,6 +526,10 @@ fn char_parser(input: &str) -> IResult<&str, char> {
     none_of("$'")(input)
 }
 
+fn wchar_parser(input: &str) -> IResult<&str, char> {
+    none_of("$\"")(input)
+}
+
 fn dec_int_parser(input: &str) -> IResult<&str, IntRepresentation> {
     map_res(
         tuple((
@@ -472,3 +595,81 @@ fn bin_int_parser(input: &str) -> IResult<&str, IntRepresentation> {
 fn bin_digit(input: &str) -> IResult<&str, &str> {
     is_a("01")(input)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::business::msg_converter::dtp_writer;
+
+    fn parse_str(var_declarations: &str) -> Result<DataType> {
+        parse(
+            format!(
+                r#"<?xml version="1.0" encoding="UTF-8"?>
+                <DataType Name="Chars">
+                    <StructuredType>
+                        {var_declarations}
+                    </StructuredType>
+                </DataType>"#
+            )
+            .as_bytes(),
+        )
+    }
+
+    #[test]
+    fn reads_what_the_dtp_writer_wrote_of_chars_up_to_a_byte() {
+        let var_declarations = vec![
+            VarDeclaration::new(
+                "chars".to_string(),
+                BaseType::CHAR,
+                Some(ArraySize::Capacity(2)),
+                Some(InitialValue::Array(vec![
+                    InitialValue::CHAR(CharRepresentation::Hexadecimal(0xFF)),
+                    InitialValue::CHAR(CharRepresentation::Char('\u{FF}')),
+                ])),
+                None,
+                Vec::new(),
+            ),
+            // only a CHAR is limited to a single byte, a STRING keeps any character
+            VarDeclaration::new(
+                "string".to_string(),
+                BaseType::STRING(None),
+                None,
+                Some(InitialValue::STRING(vec![
+                    CharRepresentation::Char('\u{20AC}'),
+                    CharRepresentation::Hexadecimal(0xFF),
+                ])),
+                None,
+                Vec::new(),
+            ),
+        ];
+        let data_type = DataType::new(
+            "Chars".to_string(),
+            None,
+            None,
+            Vec::new(),
+            None,
+            StructuredType::new(None, var_declarations),
+        );
+        let directory = std::env::temp_dir()
+            .join(format!("rossydiac-{}", std::process::id()))
+            .join("dtp_chars");
+        std::fs::create_dir_all(&directory).unwrap();
+        let directory = format!("{}/", directory.display());
+        dtp_writer::write(data_type.clone(), &directory).unwrap();
+        assert_eq!(read(&format!("{directory}Chars.dtp")).unwrap(), data_type);
+    }
+
+    #[test]
+    fn rejects_chars_beyond_a_byte() {
+        let char = |value: &str| {
+            parse_str(&format!(
+                r#"<VarDeclaration Name="c" Type="CHAR" InitialValue="'{value}'"/>"#
+            ))
+        };
+        assert!(char("&#255;").is_ok());
+        assert!(char("$FF").is_ok());
+        assert!(char("&#256;").is_err());
+        assert!(char("Ĭ").is_err());
+        assert!(char("ab").is_err());
+    }
+}
@@ -0,0 +1,316 @@
+use std::collections::BTreeSet;
+
+use crate::business::dtp_converter::msg_writer::{self, quoted_string_as_string};
+use crate::business::error::Result;
+use crate::business::file_writer;
+use crate::business::naming;
+use crate::core::msg::{
+    Annotation, ArraySize, BaseType, BoolRepresentation, Field, FieldType, InitialValue,
+    IntRepresentation, Reference, StructuredType,
+};
+
+const INDENTATION: &str = "  ";
+// rosidl adds this member to structures which would otherwise be empty
+const PLACEHOLDER_MEMBER: &str = "uint8 structure_needs_at_least_one_member;";
+
+pub fn write(msg_dto: &StructuredType, package_name: &str, to_directory: &str) -> Result<()> {
+    validate_float_literals(msg_dto)?;
+    let file_name = msg_dto.name();
+    let path_to_file = format!("{to_directory}{file_name}.idl");
+    let file_content: String = msg_dto_as_string(msg_dto, package_name);
+    file_writer::write_if_changed(&path_to_file, file_content)?;
+    Ok(())
+}
+
+// IDL has no literals for NaN and the infinities
+fn validate_float_literals(msg_dto: &StructuredType) -> Result<()> {
+    for field in msg_dto.fields() {
+        let annotation_values = field
+            .annotations()
+            .iter()
+            .flat_map(|annotation| match annotation {
+                Annotation::Range { min, max } => vec![min.as_ref(), max.as_ref()],
+                Annotation::Default(default) => vec![Some(default)],
+                _ => Vec::new(),
+            })
+            .flatten();
+        if field
+            .initial_value()
+            .into_iter()
+            .chain(annotation_values)
+            .any(has_non_finite_float)
+        {
+            return Err(format!(
+                "\"{}\" cannot be written to IDL, which has no literals for NaN and infinities",
+                field.name()
+            )
+            .into());
+        }
+    }
+    Ok(())
+}
+
+fn has_non_finite_float(initial_value: &InitialValue) -> bool {
+    match initial_value {
+        InitialValue::Float32(f32) => !f32.is_finite(),
+        InitialValue::Float64(f64) => !f64.is_finite(),
+        InitialValue::Array(elements) => elements.iter().any(has_non_finite_float),
+        _ => false,
+    }
+}
+
+fn msg_dto_as_string(msg_dto: &StructuredType, package_name: &str) -> String {
+    let name = msg_dto.name();
+    let (constants, variables): (Vec<&Field>, Vec<&Field>) = msg_dto
+        .fields()
+        .iter()
+        .partition(|field| *field.field_type() == FieldType::Constant);
+
+    let mut lines: Vec<String> = vec![
+        "// generated by RossyDiac".to_string(),
+        format!("// with input from {package_name}/msg/{name}"),
+        String::new(),
+    ];
+    let includes: BTreeSet<String> = variables
+        .iter()
+        .filter_map(|field| match field.base_type() {
+            BaseType::Custom(reference) => Some(include_as_string(reference, package_name)),
+            _ => None,
+        })
+        .collect();
+    if !includes.is_empty() {
+        lines.extend(includes);
+        lines.push(String::new());
+    }
+
+    lines.push(format!("module {package_name} {{"));
+    lines.push(format!("{INDENTATION}module msg {{"));
+    let indentation = INDENTATION.repeat(2);
+    if !constants.is_empty() {
+        lines.push(format!("{indentation}module {name}_Constants {{"));
+        for constant in constants {
+            lines.extend(
+                comment_as_lines(constant.comment())
+                    .into_iter()
+                    .chain(annotations_as_lines(constant.annotations()))
+                    .chain(std::iter::once(constant_as_string(constant, package_name)))
+                    .map(|line| format!("{indentation}{INDENTATION}{line}")),
+            );
+        }
+        lines.push(format!("{indentation}}};"));
+    }
+    lines.push(format!(
+        "{indentation}struct {} {{",
+        naming::escape_idl_keyword(name)
+    ));
+    if variables.is_empty() {
+        lines.push(format!("{indentation}{INDENTATION}{PLACEHOLDER_MEMBER}"));
+    }
+    for variable in variables {
+        lines.extend(
+            variable_as_lines(variable, package_name)
+                .into_iter()
+                .map(|line| format!("{indentation}{INDENTATION}{line}")),
+        );
+    }
+    lines.push(format!("{indentation}}};"));
+    lines.push(format!("{INDENTATION}}};"));
+    lines.push("};".to_string());
+
+    let mut result = lines.join("\n");
+    result.push('\n');
+    result
+}
+
+fn include_as_string(reference: &Reference, package_name: &str) -> String {
+    match reference {
+        Reference::Relative { file } => format!("#include \"{package_name}/msg/{file}.idl\""),
+        Reference::Absolute { package, file } => format!("#include \"{package}/msg/{file}.idl\""),
+    }
+}
+
+fn constant_as_string(field: &Field, package_name: &str) -> String {
+    let value = field
+        .initial_value()
+        .map(initial_value_as_string)
+        .unwrap_or_default();
+    format!(
+        "const {} {} = {value};",
+        base_type_as_string(field.base_type(), package_name),
+        naming::escape_idl_keyword(field.name())
+    )
+}
+
+fn variable_as_lines(field: &Field, package_name: &str) -> Vec<String> {
+    let mut lines = comment_as_lines(field.comment());
+    lines.extend(annotations_as_lines(field.annotations()));
+    if let Some(initial_value) = field.initial_value() {
+        lines.push(format!(
+            "@default (value={})",
+            initial_value_as_string(initial_value)
+        ));
+    }
+    let base_type = base_type_as_string(field.base_type(), package_name);
+    let name = naming::escape_idl_keyword(field.name());
+    lines.push(match field.array_size() {
+        None => format!("{base_type} {name};"),
+        Some(ArraySize::Capacity(capacity)) => format!("{base_type} {name}[{capacity}];"),
+        Some(ArraySize::Dynamic) => format!("sequence<{base_type}> {name};"),
+        Some(ArraySize::BoundDynamic(bound)) => format!("sequence<{base_type}, {bound}> {name};"),
+    });
+    lines
+}
+
+fn comment_as_lines(comment: Option<&String>) -> Vec<String> {
+    comment
+        .map(|comment| {
+            vec![format!(
+                "@verbatim (language=\"comment\", text={})",
+                quoted_string_as_string(comment, '"')
+            )]
+        })
+        .unwrap_or_default()
+}
+
+// The default value and the comment are written from the field itself
+fn annotations_as_lines(annotations: &[Annotation]) -> Vec<String> {
+    annotations
+        .iter()
+        .filter_map(|annotation| match annotation {
+            Annotation::Key => Some("@key".to_string()),
+            Annotation::Unit(unit) => {
+                Some(format!("@unit ({})", quoted_string_as_string(unit, '"')))
+            }
+            Annotation::Range { min, max } => {
+                let bounds: Vec<String> = [("min", min), ("max", max)]
+                    .into_iter()
+                    .filter_map(|(name, bound)| {
+                        bound
+                            .as_ref()
+                            .map(|bound| format!("{name}={}", initial_value_as_string(bound)))
+                    })
+                    .collect();
+                Some(format!("@range ({})", bounds.join(", ")))
+            }
+            Annotation::Default(_) => None,
+            Annotation::Verbatim { language, text } => match language.as_deref() {
+                None | Some("comment") => None,
+                Some(language) => Some(format!(
+                    "@verbatim (language={}, text={})",
+                    quoted_string_as_string(language, '"'),
+                    quoted_string_as_string(text, '"')
+                )),
+            },
+        })
+        .collect()
+}
+
+fn base_type_as_string(base_type: &BaseType, package_name: &str) -> String {
+    match base_type {
+        BaseType::Bool => "boolean".to_string(),
+        BaseType::Byte => "octet".to_string(),
+        BaseType::Float32 => "float".to_string(),
+        BaseType::Float64 => "double".to_string(),
+        BaseType::Int8 => "int8".to_string(),
+        BaseType::Uint8 => "uint8".to_string(),
+        BaseType::Char => "char".to_string(),
+        BaseType::Int16 => "int16".to_string(),
+        BaseType::Uint16 => "uint16".to_string(),
+        BaseType::Int32 => "int32".to_string(),
+        BaseType::Uint32 => "uint32".to_string(),
+        BaseType::Int64 => "int64".to_string(),
+        BaseType::Uint64 => "uint64".to_string(),
+        BaseType::String(bound) => bound
+            .map(|bound| format!("string<{bound}>"))
+            .unwrap_or_else(|| "string".to_string()),
+        BaseType::Wstring(bound) => bound
+            .map(|bound| format!("wstring<{bound}>"))
+            .unwrap_or_else(|| "wstring".to_string()),
+        BaseType::Custom(Reference::Relative { file }) => format!("{package_name}::msg::{file}"),
+        BaseType::Custom(Reference::Absolute { package, file }) => {
+            format!("{package}::msg::{file}")
+        }
+    }
+}
+
+fn initial_value_as_string(initial_value: &InitialValue) -> String {
+    match initial_value {
+        InitialValue::Bool(BoolRepresentation::String(bool))
+        | InitialValue::Bool(BoolRepresentation::Binary(bool)) => {
+            if *bool { "TRUE" } else { "FALSE" }.to_string()
+        }
+        InitialValue::Byte(int_representation)
+        | InitialValue::Uint8(int_representation)
+        | InitialValue::Uint16(int_representation)
+        | InitialValue::Uint32(int_representation)
+        | InitialValue::Uint64(int_representation)
+        | InitialValue::Int8(int_representation)
+        | InitialValue::Int16(int_representation)
+        | InitialValue::Int32(int_representation)
+        | InitialValue::Int64(int_representation)
+        | InitialValue::Char(int_representation) => {
+            int_representation_as_string(int_representation)
+        }
+        // the debug notation always has a fraction or an exponent, e.g. "1.0" or "1e300", so
+        // that it is read as a floating point literal again
+        InitialValue::Float32(f32) => format!("{f32:?}"),
+        InitialValue::Float64(f64) => format!("{f64:?}"),
+        InitialValue::String(string) => quoted_string_as_string(string, '"'),
+        InitialValue::Wstring(string) => format!("L{}", quoted_string_as_string(string, '"')),
+        InitialValue::Array(_) => {
+            quoted_string_as_string(&sequence_literal_as_string(initial_value), '"')
+        }
+    }
+}
+
+// sequence defaults are written like rosidl does, as a python tuple, e.g. "(1, 2, 3)"
+fn sequence_literal_as_string(initial_value: &InitialValue) -> String {
+    match initial_value {
+        InitialValue::Array(elements) if elements.len() == 1 => {
+            format!("({},)", sequence_literal_as_string(&elements[0]))
+        }
+        InitialValue::Array(elements) => format!(
+            "({})",
+            elements
+                .iter()
+                .map(sequence_literal_as_string)
+                .collect::<Vec<String>>()
+                .join(", ")
+        ),
+        InitialValue::Bool(BoolRepresentation::String(bool))
+        | InitialValue::Bool(BoolRepresentation::Binary(bool)) => {
+            if *bool { "True" } else { "False" }.to_string()
+        }
+        InitialValue::String(string) | InitialValue::Wstring(string) => {
+            quoted_string_as_string(string, '\'')
+        }
+        InitialValue::Float32(_) | InitialValue::Float64(_) => {
+            initial_value_as_string(initial_value)
+        }
+        InitialValue::Byte(int_representation)
+        | InitialValue::Uint8(int_representation)
+        | InitialValue::Uint16(int_representation)
+        | InitialValue::Uint32(int_representation)
+        | InitialValue::Uint64(int_representation)
+        | InitialValue::Int8(int_representation)
+        | InitialValue::Int16(int_representation)
+        | InitialValue::Int32(int_representation)
+        | InitialValue::Int64(int_representation)
+        | InitialValue::Char(int_representation) => {
+            msg_writer::int_representation_as_string(int_representation)
+        }
+    }
+}
+
+fn int_representation_as_string(int_representation: &IntRepresentation) -> String {
+    match int_representation {
+        IntRepresentation::SignedDecimal(i64) => format!("{i64}"),
+        // IDL does not know binary literals
+        IntRepresentation::UnsignedDecimal(u64) | IntRepresentation::Binary(u64) => {
+            format!("{u64}")
+        }
+        IntRepresentation::Octal(0) => "0".to_string(),
+        IntRepresentation::Octal(u64) => format!("0{u64:o}"),
+        IntRepresentation::Hexadecimal(u64) => format!("0x{u64:X}"),
+    }
+}
@@ -1,3 +1,4 @@
 pub mod dtp_reader;
 pub mod msg_writer;
 pub mod converter;
+pub mod idl_writer;
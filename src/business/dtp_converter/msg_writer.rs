@@ -1,6 +1,5 @@
-use std::fs;
-
 use crate::business::error::Result;
+use crate::business::file_writer;
 use crate::core::msg::{
     ArraySize, BaseType, BoolRepresentation, Field, FieldType, InitialValue, IntRepresentation,
     Reference, StructuredType,
@@ -10,7 +9,7 @@ pub fn write(msg_dto: &StructuredType, to_directory: &str) -> Result<()> {
     let file_name = msg_dto.name();
     let path_to_file = format!("{to_directory}{file_name}.msg");
     let file_content: String = msg_dto_as_string(msg_dto);
-    fs::write(path_to_file, file_content)?;
+    file_writer::write_if_changed(&path_to_file, file_content)?;
     Ok(())
 }
 
@@ -52,7 +51,7 @@ fn field_type_as_string(field_type: &FieldType, initial_value: Option<&InitialVa
     }
 }
 
-fn base_type_as_string(base_type: &BaseType) -> String {
+pub(crate) fn base_type_as_string(base_type: &BaseType) -> String {
     match base_type {
         BaseType::Bool => "bool".to_string(),
         BaseType::Byte => "byte".to_string(),
@@ -80,7 +79,7 @@ fn base_type_as_string(base_type: &BaseType) -> String {
     }
 }
 
-fn array_size_as_string(constraint: Option<&ArraySize>) -> String {
+pub(crate) fn array_size_as_string(constraint: Option<&ArraySize>) -> String {
     constraint
         .map(|array_size| match array_size {
             ArraySize::Capacity(static_capacity) => {
@@ -116,10 +115,10 @@ fn initial_value_as_string(initial_value: &InitialValue) -> String {
             int_representation_as_string(int_representation)
         },
         InitialValue::String(string_representation) => {
-            format!("'{}'", string_representation.replace("'", "\\'"))
+            quoted_string_as_string(string_representation, '\'')
         }
         InitialValue::Wstring(string_representation) => {
-            format!("\"{}\"", string_representation.replace("\"", "\\\""))
+            quoted_string_as_string(string_representation, '"')
         }
         InitialValue::Array(v) => format!(
             "[{}]",
@@ -131,6 +130,27 @@ fn initial_value_as_string(initial_value: &InitialValue) -> String {
     }
 }
 
+pub(super) fn quoted_string_as_string(string: &str, quote: char) -> String {
+    let mut quoted = String::with_capacity(string.len() + 2);
+    quoted.push(quote);
+    for c in string.chars() {
+        match c {
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\t' => quoted.push_str("\\t"),
+            '\r' => quoted.push_str("\\r"),
+            c if c == quote => {
+                quoted.push('\\');
+                quoted.push(c);
+            }
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push(quote);
+    quoted
+}
+
 fn bool_representation_as_string(bool_representation: &BoolRepresentation) -> String {
     match bool_representation {
         BoolRepresentation::String(true) => "true".to_string(),
@@ -140,7 +160,7 @@ fn bool_representation_as_string(bool_representation: &BoolRepresentation) -> St
     }
 }
 
-fn int_representation_as_string(int_representation: &IntRepresentation) -> String {
+pub(super) fn int_representation_as_string(int_representation: &IntRepresentation) -> String {
     match int_representation {
         IntRepresentation::SignedDecimal(i64) => format!("{i64}"),
         IntRepresentation::UnsignedDecimal(u64) => format!("{u64}"),
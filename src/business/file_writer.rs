@@ -0,0 +1,17 @@
+use std::fs;
+
+use log::debug;
+
+use crate::business::error::Result;
+
+/// Writes the content unless the file already has it, so that 4diac IDE does not reload
+/// unchanged types
+pub fn write_if_changed(path_to_file: &str, content: impl AsRef<[u8]>) -> Result<()> {
+    let content = content.as_ref();
+    if fs::read(path_to_file).is_ok_and(|existing_content| existing_content == content) {
+        debug!("Skipped writing the unchanged file {path_to_file:?}");
+        return Ok(());
+    }
+    fs::write(path_to_file, content)?;
+    Ok(())
+}
@@ -1,40 +1,886 @@
+use crate::business::compatibility;
 use crate::business::dtp_converter::{converter as dtp_converter, *};
-use crate::business::error::Result;
+use crate::business::error::{Error, Result};
+use crate::business::model_diff;
+use crate::business::roundtrip;
+use crate::business::source_manifest::{self, SourceManifest};
 use crate::business::msg_converter::{converter as msg_converter, *};
-use log::{debug, info};
+use crate::core::{dtp, msg, typelib};
+use log::{debug, error, info};
+use notify::{EventKind, RecursiveMode, Watcher};
+use rayon::prelude::*;
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Mutex};
+use std::time::Duration;
 
-pub fn convert_to_dtp(
-    path_to_source_file: &str,
+pub use crate::business::dtp_converter::converter::ArrayPadding;
+pub use crate::business::msg_converter::converter::TypeNaming;
+
+pub const DEFAULT_TYPE_VERSION: &str = "1.0";
+// editors usually save a file in several steps, which should trigger only one conversion
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(200);
+const SOURCE_FILE_EXTENSIONS: [&str; 4] = ["msg", "idl", "srv", "dtp"];
+
+/// How the read data structures are printed
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OutputFormat {
+    Json,
+    Yaml,
+}
+
+/// What is generated alongside the data types and how the generated types are identified
+#[derive(Clone, Debug)]
+pub struct DtpOptions {
+    pub generate_function_blocks: bool,
+    pub generate_forte_code: bool,
+    pub author: Option<String>,
+    pub organization: Option<String>,
+    pub type_version: String,
+    pub type_naming: TypeNaming,
+    pub incremental: bool,
+}
+
+/// The source files the types of one conversion run were converted from. The results are named
+/// after their types, so two types of the same name would overwrite each other's results.
+#[derive(Default)]
+struct ConvertedTypes(Mutex<HashMap<String, String>>);
+
+impl ConvertedTypes {
+    fn claim(
+        &self,
+        type_name: &str,
+        path_to_source_file: &str,
+        path_to_destination_directory: &str,
+    ) -> Result<()> {
+        let mut source_files = self.0.lock().map_err(Error::custom)?;
+        match source_files.get(type_name) {
+            Some(other_source_file) if other_source_file != path_to_source_file => {
+                Err(Error::custom(format!(
+                    "{path_to_source_file} and {other_source_file} both convert to the type \
+                    {type_name} in {path_to_destination_directory}"
+                )))
+            }
+            _ => {
+                source_files.insert(type_name.to_string(), path_to_source_file.to_string());
+                Ok(())
+            }
+        }
+    }
+
+    // a previous run may have converted a type of the same name from another package
+    fn claim_data_type(
+        &self,
+        data_type: &dtp::DataType,
+        path_to_source_file: &str,
+        path_to_destination_directory: &str,
+    ) -> Result<()> {
+        self.claim(
+            data_type.name(),
+            path_to_source_file,
+            path_to_destination_directory,
+        )?;
+        let path_to_file = format!("{path_to_destination_directory}{}.dtp", data_type.name());
+        if !Path::new(&path_to_file).exists() {
+            return Ok(());
+        }
+        let package = |data_type: &dtp::DataType| {
+            data_type
+                .compiler_info()
+                .and_then(|compiler_info| compiler_info.package_name())
+                .cloned()
+        };
+        // an unreadable file is overwritten like any other outdated result
+        let Ok(existing_data_type) = dtp_reader::read(&path_to_file) else {
+            return Ok(());
+        };
+        let existing_package = package(&existing_data_type);
+        if existing_package != package(data_type) {
+            return Err(Error::custom(format!(
+                "Converting {path_to_source_file} would overwrite {path_to_file} of the package {}",
+                existing_package.as_deref().unwrap_or("<none>")
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl Default for DtpOptions {
+    fn default() -> Self {
+        Self {
+            generate_function_blocks: false,
+            generate_forte_code: false,
+            author: None,
+            organization: None,
+            type_version: DEFAULT_TYPE_VERSION.to_string(),
+            type_naming: TypeNaming::Mangled,
+            incremental: false,
+        }
+    }
+}
+
+pub fn convert_to_typelib(
+    paths_to_source_files: &[String],
     path_to_destination_directory: &str,
     package_name: &str,
+    options: &DtpOptions,
 ) -> Result<()> {
-    let msg_dto = msg_reader::read(path_to_source_file)?;
-    debug!("msg_dto: {:#?}", msg_dto);
-    let dtp_dto = msg_converter::convert(package_name, &msg_dto)?;
-    debug!("dtp_dto: {:#?}", dtp_dto);
-    dtp_writer::write(dtp_dto, path_to_destination_directory)?;
+    let path_to_library_directory = format!("{path_to_destination_directory}{package_name}/");
+    let path_to_typelib_directory =
+        format!("{path_to_library_directory}{}/", typelib::TYPELIB_DIRECTORY);
+    fs::create_dir_all(&path_to_typelib_directory)?;
+    let manifest = typelib::Manifest::new(
+        package_name.to_string(),
+        package_name.to_string(),
+        Some(format!("Data types of the ROS 2 package {package_name}")),
+        options.type_version.clone(),
+    );
+    typelib_writer::write(&manifest, &path_to_library_directory)?;
+    convert_all_to_dtp(
+        paths_to_source_files,
+        &path_to_typelib_directory,
+        package_name,
+        options,
+    )
+}
+
+pub fn convert_all_to_dtp(
+    paths_to_source_files: &[String],
+    path_to_destination_directory: &str,
+    package_name: &str,
+    options: &DtpOptions,
+) -> Result<()> {
+    let mut source_manifest = options
+        .incremental
+        .then(|| SourceManifest::read(path_to_destination_directory));
+    let settings = format!("{package_name} {options:?}");
+    let hashes: Vec<Result<Option<String>>> = paths_to_source_files
+        .par_iter()
+        .map(|path_to_source_file| {
+            source_manifest
+                .as_ref()
+                .map(|_| source_manifest::hash(path_to_source_file, &settings))
+                .transpose()
+        })
+        .collect();
+    let converted_types = ConvertedTypes::default();
+    // the types of the skipped files are claimed first, so that no other file overwrites them
+    let mut are_skipped = Vec::new();
+    for (path_to_source_file, hash) in paths_to_source_files.iter().zip(&hashes) {
+        let skipped_outputs = match (&source_manifest, hash) {
+            (Some(source_manifest), Ok(Some(hash)))
+                if source_manifest.is_up_to_date(
+                    path_to_destination_directory,
+                    path_to_source_file,
+                    hash,
+                ) =>
+            {
+                Some(source_manifest.outputs(path_to_source_file))
+            }
+            _ => None,
+        };
+        for type_name in skipped_outputs
+            .into_iter()
+            .flatten()
+            .filter_map(|output| output.strip_suffix(".dtp"))
+        {
+            converted_types.claim(
+                type_name,
+                path_to_source_file,
+                path_to_destination_directory,
+            )?;
+        }
+        are_skipped.push(skipped_outputs.is_some());
+    }
+    // the results keep the order of the source files, so the report does not depend on the scheduling
+    let results: Vec<Result<Option<Conversion>>> = paths_to_source_files
+        .par_iter()
+        .zip(hashes)
+        .zip(are_skipped)
+        .map(|((path_to_source_file, hash), is_skipped)| {
+            if is_skipped {
+                info!("Skipped the unchanged file {path_to_source_file:?}");
+                return Ok(None);
+            }
+            let hash = hash?;
+            let paths_to_outputs = convert_to_dtp(
+                path_to_source_file,
+                path_to_destination_directory,
+                package_name,
+                options,
+                &converted_types,
+            )?;
+            Ok(hash.map(|hash| Conversion {
+                hash,
+                paths_to_outputs,
+            }))
+        })
+        .collect();
+
+    let mut failures = Vec::new();
+    for (path_to_source_file, result) in paths_to_source_files.iter().zip(results) {
+        match (result, &mut source_manifest) {
+            (Ok(Some(conversion)), Some(source_manifest)) => source_manifest.record(
+                path_to_source_file,
+                conversion.hash,
+                &conversion.paths_to_outputs,
+            ),
+            (Ok(_), _) => {}
+            (Err(error), _) => failures.push((path_to_source_file, error)),
+        }
+    }
+    // the files converted despite a failure need not be converted again either
+    if let Some(source_manifest) = &source_manifest {
+        source_manifest.write(path_to_destination_directory)?;
+    }
+    if options.generate_forte_code {
+        forte_writer::write_support_headers(path_to_destination_directory)?;
+    }
+    if paths_to_source_files.len() == 1 {
+        if let Some((_, error)) = failures.pop() {
+            return Err(error);
+        }
+    }
+    if !failures.is_empty() {
+        for (path_to_source_file, error) in &failures {
+            error!("Failed to convert {path_to_source_file}: {error}");
+        }
+        return Err(Error::custom(format!(
+            "{} of {} files failed to convert, see the logged errors",
+            failures.len(),
+            paths_to_source_files.len()
+        )));
+    }
     Ok(())
 }
 
+// the conversion of a source file, as recorded in the source manifest
+struct Conversion {
+    hash: String,
+    paths_to_outputs: Vec<String>,
+}
+
+fn convert_to_dtp(
+    path_to_source_file: &str,
+    path_to_destination_directory: &str,
+    package_name: &str,
+    options: &DtpOptions,
+    converted_types: &ConvertedTypes,
+) -> Result<Vec<String>> {
+    let version_info = version_info_generator::generate(
+        options.author.as_deref(),
+        options.organization.as_deref(),
+        &options.type_version,
+        path_to_source_file,
+    )?;
+    if has_extension(path_to_source_file, "srv") {
+        return convert_service_to_dtp(
+            path_to_source_file,
+            path_to_destination_directory,
+            package_name,
+            &version_info,
+            options,
+            converted_types,
+        );
+    }
+    let mut paths_to_outputs = Vec::new();
+    for msg_dto in read_msg_dtos(path_to_source_file)? {
+        debug!("msg_dto: {:#?}", msg_dto);
+        let dtp_dto = msg_converter::convert(
+            package_name,
+            &msg_dto,
+            &version_info,
+            options.type_naming,
+        )?;
+        debug!("dtp_dto: {:#?}", dtp_dto);
+        converted_types.claim_data_type(
+            &dtp_dto,
+            path_to_source_file,
+            path_to_destination_directory,
+        )?;
+        if options.generate_function_blocks {
+            for fb_type in fbt_generator::generate(&dtp_dto) {
+                paths_to_outputs.push(fbt_writer::write(&fb_type, path_to_destination_directory)?);
+            }
+        }
+        if options.generate_forte_code {
+            paths_to_outputs.push(forte_writer::write(
+                package_name,
+                &msg_dto,
+                &dtp_dto,
+                path_to_destination_directory,
+            )?);
+        }
+        paths_to_outputs.push(dtp_writer::write(dtp_dto, path_to_destination_directory)?);
+    }
+    Ok(paths_to_outputs)
+}
+
+fn convert_service_to_dtp(
+    path_to_source_file: &str,
+    path_to_destination_directory: &str,
+    package_name: &str,
+    version_info: &dtp::VersionInfo,
+    options: &DtpOptions,
+    converted_types: &ConvertedTypes,
+) -> Result<Vec<String>> {
+    let service_dto = msg_reader::read_service(path_to_source_file)?;
+    debug!("service_dto: {:#?}", service_dto);
+    let (request_dtp_dto, response_dtp_dto) = msg_converter::convert_service(
+        package_name,
+        &service_dto,
+        version_info,
+        options.type_naming,
+    )?;
+    debug!("request_dtp_dto: {:#?}", request_dtp_dto);
+    debug!("response_dtp_dto: {:#?}", response_dtp_dto);
+    for dtp_dto in [&request_dtp_dto, &response_dtp_dto] {
+        converted_types.claim_data_type(
+            dtp_dto,
+            path_to_source_file,
+            path_to_destination_directory,
+        )?;
+    }
+    let adapter_type = adp_generator::generate(
+        msg_converter::convert_service_name(package_name, &service_dto, options.type_naming),
+        &request_dtp_dto,
+        &response_dtp_dto,
+    );
+    converted_types.claim(
+        adapter_type.name(),
+        path_to_source_file,
+        path_to_destination_directory,
+    )?;
+    let mut paths_to_outputs = vec![adp_writer::write(
+        &adapter_type,
+        path_to_destination_directory,
+    )?];
+    if options.generate_function_blocks {
+        for fb_type in fbt_generator::generate(&request_dtp_dto)
+            .into_iter()
+            .chain(fbt_generator::generate(&response_dtp_dto))
+        {
+            paths_to_outputs.push(fbt_writer::write(&fb_type, path_to_destination_directory)?);
+        }
+    }
+    if options.generate_forte_code {
+        paths_to_outputs.extend(forte_writer::write_service(
+            package_name,
+            &service_dto,
+            &request_dtp_dto,
+            &response_dtp_dto,
+            path_to_destination_directory,
+        )?);
+    }
+    paths_to_outputs.push(dtp_writer::write(request_dtp_dto, path_to_destination_directory)?);
+    paths_to_outputs.push(dtp_writer::write(response_dtp_dto, path_to_destination_directory)?);
+    Ok(paths_to_outputs)
+}
+
 pub fn convert_to_msg(
     path_to_source_file: &str,
     path_to_destination_directory: &str,
     package_name: &str,
+    array_padding: ArrayPadding,
 ) -> Result<()> {
     let dtp_dto = dtp_reader::read(path_to_source_file)?;
     debug!("dtp_dto: {:#?}", dtp_dto);
-    let msg_dto = dtp_converter::convert(package_name, &dtp_dto)?;
+    let msg_dto = dtp_converter::convert(package_name, &dtp_dto, array_padding)?;
     debug!("msg_dto: {:#?}", msg_dto);
     msg_writer::write(&msg_dto, path_to_destination_directory)?;
     Ok(())
 }
 
-pub fn print_dtp(path_to_source_file: &str) -> Result<()> {
-    info!("{:#?}", dtp_reader::read(path_to_source_file)?);
+pub fn convert_to_idl(
+    path_to_source_file: &str,
+    path_to_destination_directory: &str,
+    package_name: &str,
+    array_padding: ArrayPadding,
+) -> Result<()> {
+    let dtp_dto = dtp_reader::read(path_to_source_file)?;
+    debug!("dtp_dto: {:#?}", dtp_dto);
+    let msg_dto = dtp_converter::convert(package_name, &dtp_dto, array_padding)?;
+    debug!("msg_dto: {:#?}", msg_dto);
+    idl_writer::write(&msg_dto, package_name, path_to_destination_directory)?;
+    Ok(())
+}
+
+pub fn verify_roundtrip(
+    path_to_source_file: &str,
+    package_name: &str,
+    array_padding: ArrayPadding,
+) -> Result<()> {
+    let differences = if has_extension(path_to_source_file, "dtp") {
+        verify_dtp_roundtrip(path_to_source_file, package_name, array_padding)?
+    } else {
+        verify_msg_roundtrip(path_to_source_file, package_name, array_padding)?
+    };
+    if !differences.is_empty() {
+        for difference in &differences {
+            error!("{difference}");
+        }
+        return Err(Error::custom(format!(
+            "The round trip of {path_to_source_file} changed its meaning, see the logged differences"
+        )));
+    }
+    info!("The round trip of {path_to_source_file} preserved its meaning");
+    Ok(())
+}
+
+fn verify_msg_roundtrip(
+    path_to_source_file: &str,
+    package_name: &str,
+    array_padding: ArrayPadding,
+) -> Result<Vec<roundtrip::Difference>> {
+    let version_info = version_info_generator::generate(
+        None,
+        None,
+        DEFAULT_TYPE_VERSION,
+        path_to_source_file,
+    )?;
+    let msg_dtos = if has_extension(path_to_source_file, "srv") {
+        let service_dto = msg_reader::read_service(path_to_source_file)?;
+        vec![service_dto.request().clone(), service_dto.response().clone()]
+    } else {
+        read_msg_dtos(path_to_source_file)?
+    };
+    let mut differences = Vec::new();
+    for msg_dto in msg_dtos {
+        let dtp_dto =
+            msg_converter::convert(package_name, &msg_dto, &version_info, TypeNaming::Mangled)?;
+        debug!("dtp_dto: {:#?}", dtp_dto);
+        let round_tripped_msg_dto = dtp_converter::convert(package_name, &dtp_dto, array_padding)?;
+        debug!("round_tripped_msg_dto: {:#?}", round_tripped_msg_dto);
+        differences.append(&mut roundtrip::diff_msg(&msg_dto, &round_tripped_msg_dto));
+    }
+    Ok(differences)
+}
+
+fn verify_dtp_roundtrip(
+    path_to_source_file: &str,
+    package_name: &str,
+    array_padding: ArrayPadding,
+) -> Result<Vec<roundtrip::Difference>> {
+    let dtp_dto = dtp_reader::read(path_to_source_file)?;
+    let msg_dto = dtp_converter::convert(package_name, &dtp_dto, array_padding)?;
+    debug!("msg_dto: {:#?}", msg_dto);
+    let version_info = version_info_generator::generate(
+        None,
+        None,
+        DEFAULT_TYPE_VERSION,
+        path_to_source_file,
+    )?;
+    let type_naming = match dtp_dto.package_name() {
+        Some(_) => TypeNaming::Packaged,
+        None => TypeNaming::Mangled,
+    };
+    let round_tripped_dtp_dto =
+        msg_converter::convert(package_name, &msg_dto, &version_info, type_naming)?;
+    debug!("round_tripped_dtp_dto: {:#?}", round_tripped_dtp_dto);
+    roundtrip::diff_dtp(package_name, &dtp_dto, &round_tripped_dtp_dto)
+}
+
+pub fn diff(
+    path_to_old_file: &str,
+    path_to_new_file: &str,
+    package_name: &str,
+    array_padding: ArrayPadding,
+) -> Result<()> {
+    let old_msg_dto = read_as_msg_dto(path_to_old_file, package_name, array_padding)?;
+    let new_msg_dto = read_as_msg_dto(path_to_new_file, package_name, array_padding)?;
+    let changes = model_diff::diff(&old_msg_dto, &new_msg_dto);
+    if changes.is_empty() {
+        println!("{path_to_old_file} and {path_to_new_file} have the same meaning");
+    }
+    for change in changes {
+        println!("{change}");
+    }
+    Ok(())
+}
+
+pub fn check_compat(
+    path_to_old_file: &str,
+    path_to_new_file: &str,
+    package_name: &str,
+    array_padding: ArrayPadding,
+) -> Result<()> {
+    let old_msg_dto = read_as_msg_dto(path_to_old_file, package_name, array_padding)?;
+    let new_msg_dto = read_as_msg_dto(path_to_new_file, package_name, array_padding)?;
+    let report = compatibility::check(&old_msg_dto, &new_msg_dto);
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&report).map_err(Error::custom)?
+    );
+    if !report.compatible() {
+        return Err(Error::custom(format!(
+            "{path_to_new_file} breaks applications using {path_to_old_file}"
+        )));
+    }
     Ok(())
 }
 
-pub fn print_msg(path_to_source_file: &str) -> Result<()> {
-    info!("{:#?}", msg_reader::read(path_to_source_file)?);
+// DTP files are compared in their converted form, so that they can be compared with MSG files
+fn read_as_msg_dto(
+    path_to_source_file: &str,
+    package_name: &str,
+    array_padding: ArrayPadding,
+) -> Result<msg::StructuredType> {
+    if has_extension(path_to_source_file, "dtp") {
+        let dtp_dto = dtp_reader::read(path_to_source_file)?;
+        return dtp_converter::convert(package_name, &dtp_dto, array_padding);
+    }
+    if has_extension(path_to_source_file, "srv") {
+        return Err(Error::custom(format!(
+            "{path_to_source_file} is a service, only messages and data types can be compared"
+        )));
+    }
+    let mut msg_dtos = read_msg_dtos(path_to_source_file)?;
+    if msg_dtos.len() != 1 {
+        return Err(Error::custom(format!(
+            "{path_to_source_file} defines {} types, exactly one is expected to compare",
+            msg_dtos.len()
+        )));
+    }
+    Ok(msg_dtos.remove(0))
+}
+
+pub fn watch(
+    path_to_source_directory: &str,
+    path_to_destination_directory: &str,
+    package_name: &str,
+    options: &DtpOptions,
+    array_padding: ArrayPadding,
+) -> Result<()> {
+    let source_directory = fs::canonicalize(path_to_source_directory)?;
+    fs::create_dir_all(path_to_destination_directory)?;
+    // converting the results back would overwrite the watched source files
+    if fs::canonicalize(path_to_destination_directory)? == source_directory {
+        return Err("The destination directory must differ from the watched directory".into());
+    }
+    let (sender, receiver) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(sender).map_err(Error::custom)?;
+    watcher
+        .watch(&source_directory, RecursiveMode::NonRecursive)
+        .map_err(Error::custom)?;
+    println!("Watching {} for changes", source_directory.display());
+
+    let convert = |paths_to_source_files: &BTreeSet<PathBuf>| {
+        let converted_types = ConvertedTypes::default();
+        for path_to_source_file in paths_to_source_files {
+            let path_to_source_file = path_to_source_file.to_string_lossy();
+            let result = if has_extension(&path_to_source_file, "dtp") {
+                convert_to_msg(
+                    &path_to_source_file,
+                    path_to_destination_directory,
+                    package_name,
+                    array_padding,
+                )
+            } else {
+                convert_to_dtp(
+                    &path_to_source_file,
+                    path_to_destination_directory,
+                    package_name,
+                    options,
+                    &converted_types,
+                )
+                .map(|_| ())
+            };
+            match result {
+                Ok(()) => println!("Converted {path_to_source_file}"),
+                Err(error) => error!("Failed to convert {path_to_source_file}: {error}"),
+            }
+        }
+        if options.generate_forte_code {
+            if let Err(error) = forte_writer::write_support_headers(path_to_destination_directory) {
+                error!("Failed to write the FORTE support headers: {error}");
+            }
+        }
+    };
+    convert(&source_files(&source_directory)?);
+    loop {
+        let mut changed_files = BTreeSet::new();
+        let event = receiver.recv().map_err(Error::custom)?;
+        collect_changed_files(&mut changed_files, event);
+        while let Ok(event) = receiver.recv_timeout(WATCH_DEBOUNCE) {
+            collect_changed_files(&mut changed_files, event);
+        }
+        if changed_files.is_empty() {
+            continue;
+        }
+        let mut paths_to_source_files = changed_files.clone();
+        for source_file in source_files(&source_directory)? {
+            if changed_files
+                .iter()
+                .any(|changed_file| references(&source_file, changed_file, package_name))
+            {
+                paths_to_source_files.insert(source_file);
+            }
+        }
+        convert(&paths_to_source_files);
+    }
+}
+
+fn collect_changed_files(
+    changed_files: &mut BTreeSet<PathBuf>,
+    event: notify::Result<notify::Event>,
+) {
+    match event {
+        Ok(event) if matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) => {
+            changed_files.extend(
+                event
+                    .paths
+                    .into_iter()
+                    .filter(|path| path.is_file() && is_source_file(path)),
+            );
+        }
+        Ok(_) => {}
+        Err(error) => error!("Failed to watch for changes: {error}"),
+    }
+}
+
+fn source_files(directory: &Path) -> Result<BTreeSet<PathBuf>> {
+    let mut source_files = BTreeSet::new();
+    for entry in fs::read_dir(directory)? {
+        let path = entry?.path();
+        if path.is_file() && is_source_file(&path) {
+            source_files.insert(path);
+        }
+    }
+    Ok(source_files)
+}
+
+fn is_source_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| SOURCE_FILE_EXTENSIONS.contains(&extension))
+}
+
+// Unreadable files reference nothing, their own errors are reported when they are converted
+fn references(
+    path_to_source_file: &Path,
+    path_to_referenced_file: &Path,
+    package_name: &str,
+) -> bool {
+    let Some(referenced_name) = path_to_referenced_file
+        .file_stem()
+        .and_then(|file_stem| file_stem.to_str())
+    else {
+        return false;
+    };
+    let path_to_source_file = path_to_source_file.to_string_lossy();
+    let is_dtp = |path: &str| has_extension(path, "dtp");
+    if is_dtp(&path_to_source_file) != is_dtp(&path_to_referenced_file.to_string_lossy()) {
+        return false;
+    }
+    if is_dtp(&path_to_source_file) {
+        return dtp_reader::read(&path_to_source_file).is_ok_and(|dtp_dto| {
+            dtp_dto
+                .structured_type()
+                .var_declarations()
+                .iter()
+                .any(|var_declaration| match var_declaration.base_type() {
+                    dtp::BaseType::Custom(type_name) => type_name.name() == referenced_name,
+                    _ => false,
+                })
+        });
+    }
+    let msg_dtos = if has_extension(&path_to_source_file, "srv") {
+        msg_reader::read_service(&path_to_source_file).map(|service_dto| {
+            vec![service_dto.request().clone(), service_dto.response().clone()]
+        })
+    } else {
+        read_msg_dtos(&path_to_source_file)
+    };
+    msg_dtos.is_ok_and(|msg_dtos| {
+        msg_dtos
+            .iter()
+            .flat_map(|msg_dto| msg_dto.fields())
+            .any(|field| match field.base_type() {
+                msg::BaseType::Custom(msg::Reference::Relative { file }) => file == referenced_name,
+                msg::BaseType::Custom(msg::Reference::Absolute { package, file }) => {
+                    package == package_name && file == referenced_name
+                }
+                _ => false,
+            })
+    })
+}
+
+pub fn print_dtp(path_to_source_file: &str, format: OutputFormat) -> Result<()> {
+    print(&[dtp_reader::read(path_to_source_file)?], format)
+}
+
+/// Prints one object per message, so a service is printed as its request and its response
+pub fn print_msg(path_to_source_file: &str, format: OutputFormat) -> Result<()> {
+    let msg_dtos = if has_extension(path_to_source_file, "srv") {
+        let service_dto = msg_reader::read_service(path_to_source_file)?;
+        vec![service_dto.request().clone(), service_dto.response().clone()]
+    } else {
+        read_msg_dtos(path_to_source_file)?
+    };
+    print(&msg_dtos, format)
+}
+
+// the objects are printed one after another as JSON values or YAML documents, so that a file
+// with a single message is printed the same way as a file with several ones
+fn print(values: &[impl Serialize], format: OutputFormat) -> Result<()> {
+    for value in values {
+        let output = match format {
+            OutputFormat::Json => serde_json::to_string_pretty(value).map_err(Error::custom)?,
+            // serde_yaml cannot serialize nested enums like the representations of initial values,
+            // so YAML is printed from the JSON value, which also keeps both formats alike
+            OutputFormat::Yaml => {
+                let value = serde_json::to_value(value).map_err(Error::custom)?;
+                format!("---\n{}", serde_yaml::to_string(&value).map_err(Error::custom)?)
+            }
+        };
+        println!("{}", output.trim_end());
+    }
     Ok(())
 }
+
+fn read_msg_dtos(path_to_source_file: &str) -> Result<Vec<msg::StructuredType>> {
+    if has_extension(path_to_source_file, "idl") {
+        idl_reader::read(path_to_source_file)
+    } else {
+        Ok(vec![msg_reader::read(path_to_source_file)?])
+    }
+}
+
+fn has_extension(path_to_file: &str, extension: &str) -> bool {
+    Path::new(path_to_file)
+        .extension()
+        .is_some_and(|os_str| os_str == extension)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_directory(name: &str) -> PathBuf {
+        let directory = std::env::temp_dir()
+            .join(format!("rossydiac-{}", std::process::id()))
+            .join(name);
+        if directory.exists() {
+            fs::remove_dir_all(&directory).unwrap();
+        }
+        fs::create_dir_all(directory.join("a")).unwrap();
+        fs::create_dir_all(directory.join("b")).unwrap();
+        fs::create_dir_all(directory.join("out")).unwrap();
+        fs::write(directory.join("a").join("Foo.msg"), "int8 x\n").unwrap();
+        fs::write(directory.join("b").join("Foo.msg"), "int16 y\n").unwrap();
+        directory
+    }
+
+    fn convert(directory: &Path, source_files: &[&str], package_name: &str) -> Result<()> {
+        let paths_to_source_files: Vec<String> = source_files
+            .iter()
+            .map(|source_file| directory.join(source_file).display().to_string())
+            .collect();
+        convert_all_to_dtp(
+            &paths_to_source_files,
+            &format!("{}/", directory.join("out").display()),
+            package_name,
+            &DtpOptions {
+                type_naming: TypeNaming::Packaged,
+                ..DtpOptions::default()
+            },
+        )
+    }
+
+    #[test]
+    fn types_of_the_same_name_do_not_overwrite_each_other() {
+        let directory = test_directory("same_name");
+        assert!(convert(&directory, &["a/Foo.msg", "b/Foo.msg"], "pkg").is_err());
+    }
+
+    #[test]
+    fn types_of_another_package_are_not_overwritten() {
+        let directory = test_directory("other_package");
+        convert(&directory, &["a/Foo.msg"], "pkg_a").unwrap();
+        convert(&directory, &["a/Foo.msg"], "pkg_a").unwrap();
+        assert!(convert(&directory, &["b/Foo.msg"], "pkg_b").is_err());
+    }
+
+    #[test]
+    fn incremental_conversions_restore_missing_outputs() {
+        let directory = test_directory("incremental");
+        let path_to_destination_directory = format!("{}/", directory.join("out").display());
+        let convert_incrementally = |source_file: &str| {
+            convert_all_to_dtp(
+                &[directory.join(source_file).display().to_string()],
+                &path_to_destination_directory,
+                "pkg",
+                &DtpOptions {
+                    incremental: true,
+                    ..DtpOptions::default()
+                },
+            )
+            .unwrap()
+        };
+        let path_to_output = directory.join("out").join("ROS2_pkg_msg_Foo.dtp");
+        convert_incrementally("a/Foo.msg");
+        assert!(path_to_output.exists());
+        fs::remove_file(&path_to_output).unwrap();
+        convert_incrementally("a/Foo.msg");
+        assert!(path_to_output.exists());
+        // another path to the same source file must find its entry in the manifest
+        convert_incrementally("a/../a/./Foo.msg");
+        let manifest = fs::read_to_string(
+            directory
+                .join("out")
+                .join(source_manifest::SOURCE_MANIFEST_FILE),
+        )
+        .unwrap();
+        assert_eq!(manifest.matches("Foo.msg").count(), 1);
+    }
+
+    #[test]
+    fn skipped_files_keep_their_types() {
+        let directory = test_directory("skipped");
+        let convert_incrementally = |source_files: &[&str]| {
+            let paths_to_source_files: Vec<String> = source_files
+                .iter()
+                .map(|source_file| directory.join(source_file).display().to_string())
+                .collect();
+            convert_all_to_dtp(
+                &paths_to_source_files,
+                &format!("{}/", directory.join("out").display()),
+                "pkg",
+                &DtpOptions {
+                    incremental: true,
+                    ..DtpOptions::default()
+                },
+            )
+        };
+        convert_incrementally(&["a/Foo.msg"]).unwrap();
+        assert!(convert_incrementally(&["a/Foo.msg", "b/Foo.msg"]).is_err());
+        let path_to_output = directory.join("out").join("ROS2_pkg_msg_Foo.dtp");
+        assert!(fs::read_to_string(path_to_output).unwrap().contains("Name=\"x\""));
+    }
+
+    #[test]
+    fn the_dtp_round_trip_ignores_what_the_converter_adds() {
+        let directory = test_directory("dtp_roundtrip");
+        let path_to_source_file = directory.join("Authored.dtp");
+        fs::write(
+            &path_to_source_file,
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<DataType Name="Authored">
+  <StructuredType>
+    <VarDeclaration Name="values" Type="DINT" ArraySize="5" InitialValue="[1, 2, 3]"/>
+    <VarDeclaration Name="point" Type="Point"/>
+    <VarDeclaration Name="pose" Type="ROS2_pkg_msg_Pose"/>
+    <VarDeclaration Name="flags" Type="BOOL" ArraySize="0..2" InitialValue="[TRUE]"/>
+  </StructuredType>
+</DataType>
+"#,
+        )
+        .unwrap();
+        let path_to_source_file = path_to_source_file.display().to_string();
+        let differences =
+            verify_dtp_roundtrip(&path_to_source_file, "pkg", ArrayPadding::Pad).unwrap();
+        assert!(differences.is_empty(), "{differences:?}");
+    }
+}
@@ -1,4 +1,13 @@
+mod compatibility;
 pub mod error;
+mod file_writer;
 pub mod handler;
-mod dtp_converter;
-mod msg_converter;
+pub mod dtp_converter;
+pub mod msg_converter;
+mod model_diff;
+mod naming;
+#[cfg(test)]
+mod proptests;
+mod roundtrip;
+mod source_manifest;
+mod validation;
@@ -0,0 +1,349 @@
+use std::fmt;
+
+use crate::business::dtp_converter::msg_writer;
+use crate::business::roundtrip;
+use crate::core::msg;
+
+/// A change between two versions of a message, named after the field in the new version
+#[derive(Clone, Debug, PartialEq)]
+pub enum Change {
+    Added {
+        name: String,
+    },
+    Removed {
+        name: String,
+    },
+    Renamed {
+        from: String,
+        to: String,
+    },
+    Moved {
+        name: String,
+        from: usize,
+        to: usize,
+    },
+    FieldTypeChanged {
+        name: String,
+        from: msg::FieldType,
+        to: msg::FieldType,
+    },
+    BaseTypeChanged {
+        name: String,
+        from: msg::BaseType,
+        to: msg::BaseType,
+    },
+    ArraySizeChanged {
+        name: String,
+        from: Option<msg::ArraySize>,
+        to: Option<msg::ArraySize>,
+    },
+    InitialValueChanged {
+        name: String,
+        from: Option<msg::InitialValue>,
+        to: Option<msg::InitialValue>,
+    },
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Change::Added { name } => write!(f, "field {name} was added"),
+            Change::Removed { name } => write!(f, "field {name} was removed"),
+            Change::Renamed { from, to } => write!(f, "field {from} was renamed to {to}"),
+            Change::Moved { name, from, to } => {
+                write!(f, "field {name} moved from position {from} to {to}")
+            }
+            Change::FieldTypeChanged { name, from, to } => write!(
+                f,
+                "field {name} changed from a {} to a {}",
+                field_type(from),
+                field_type(to)
+            ),
+            Change::BaseTypeChanged { name, from, to } => write!(
+                f,
+                "field {name} changed its type from {} to {}",
+                msg_writer::base_type_as_string(from),
+                msg_writer::base_type_as_string(to)
+            ),
+            Change::ArraySizeChanged { name, from, to } => write!(
+                f,
+                "field {name} changed its array size from {} to {}",
+                array_size(from.as_ref()),
+                array_size(to.as_ref())
+            ),
+            Change::InitialValueChanged { name, from, to } => write!(
+                f,
+                "field {name} changed its initial value from {} to {}",
+                roundtrip::optional_msg_value(from.as_ref()),
+                roundtrip::optional_msg_value(to.as_ref())
+            ),
+        }
+    }
+}
+
+fn field_type(field_type: &msg::FieldType) -> &'static str {
+    match field_type {
+        msg::FieldType::Variable => "variable",
+        msg::FieldType::Constant => "constant",
+    }
+}
+
+fn array_size(array_size: Option<&msg::ArraySize>) -> String {
+    match array_size {
+        None => "none".to_string(),
+        Some(_) => msg_writer::array_size_as_string(array_size),
+    }
+}
+
+/// Compares the fields of two versions of a message. Fields are matched by name, a removed field
+/// which looks exactly like an added one, and like no other removed or added field, is considered
+/// renamed. Comments are not compared and initial values are compared by their value, so e.g.
+/// 0x10 and 16 are considered equal.
+pub fn diff(old: &msg::StructuredType, new: &msg::StructuredType) -> Vec<Change> {
+    let old_fields = old.fields();
+    let new_fields = new.fields();
+    let mut matches: Vec<Option<usize>> = old_fields
+        .iter()
+        .map(|old_field| {
+            new_fields
+                .iter()
+                .position(|new_field| new_field.name() == old_field.name())
+        })
+        .collect();
+    let removed: Vec<usize> = (0..old_fields.len())
+        .filter(|old_index| matches[*old_index].is_none())
+        .collect();
+    let added: Vec<usize> = (0..new_fields.len())
+        .filter(|new_index| !matches.contains(&Some(*new_index)))
+        .collect();
+    let look_alikes = |old_index: usize| {
+        added.iter().copied().filter(move |new_index| {
+            looks_alike(&old_fields[old_index], &new_fields[*new_index])
+        })
+    };
+    for old_index in &removed {
+        let [new_index] = look_alikes(*old_index).collect::<Vec<_>>()[..] else {
+            continue;
+        };
+        let ambiguous = removed.iter().any(|other_index| {
+            other_index != old_index && look_alikes(*other_index).any(|i| i == new_index)
+        });
+        if !ambiguous {
+            matches[*old_index] = Some(new_index);
+        }
+    }
+
+    // the largest group of matched fields which kept their order is considered unmoved, so that
+    // moving a single field does not count as moving the fields it passes
+    let matched_new_indexes: Vec<usize> = matches.iter().flatten().copied().collect();
+    let unmoved = longest_increasing_subsequence(&matched_new_indexes);
+    let mut changes = Vec::new();
+    for (old_index, old_field) in old_fields.iter().enumerate() {
+        let Some(new_index) = matches[old_index] else {
+            changes.push(Change::Removed {
+                name: old_field.name().to_string(),
+            });
+            continue;
+        };
+        let new_field = &new_fields[new_index];
+        let name = new_field.name().to_string();
+        if old_field.name() != new_field.name() {
+            changes.push(Change::Renamed {
+                from: old_field.name().to_string(),
+                to: name.clone(),
+            });
+        }
+        if !unmoved.contains(&new_index) {
+            changes.push(Change::Moved {
+                name: name.clone(),
+                from: old_index,
+                to: new_index,
+            });
+        }
+        if old_field.field_type() != new_field.field_type() {
+            changes.push(Change::FieldTypeChanged {
+                name: name.clone(),
+                from: old_field.field_type().clone(),
+                to: new_field.field_type().clone(),
+            });
+        }
+        if old_field.base_type() != new_field.base_type() {
+            changes.push(Change::BaseTypeChanged {
+                name: name.clone(),
+                from: old_field.base_type().clone(),
+                to: new_field.base_type().clone(),
+            });
+        }
+        if old_field.array_size() != new_field.array_size() {
+            changes.push(Change::ArraySizeChanged {
+                name: name.clone(),
+                from: old_field.array_size().cloned(),
+                to: new_field.array_size().cloned(),
+            });
+        }
+        if roundtrip::optional_msg_value(old_field.initial_value())
+            != roundtrip::optional_msg_value(new_field.initial_value())
+        {
+            changes.push(Change::InitialValueChanged {
+                name,
+                from: old_field.initial_value().cloned(),
+                to: new_field.initial_value().cloned(),
+            });
+        }
+    }
+    for (new_index, new_field) in new_fields.iter().enumerate() {
+        if !matches.contains(&Some(new_index)) {
+            changes.push(Change::Added {
+                name: new_field.name().to_string(),
+            });
+        }
+    }
+    changes
+}
+
+// returns the values of one of the longest subsequences of distinct values in ascending order
+fn longest_increasing_subsequence(values: &[usize]) -> Vec<usize> {
+    // lengths[i] is the length of the longest such subsequence ending with values[i]
+    let mut lengths = vec![1; values.len()];
+    let mut predecessors = vec![None; values.len()];
+    for i in 0..values.len() {
+        for j in 0..i {
+            if values[j] < values[i] && lengths[j] + 1 > lengths[i] {
+                lengths[i] = lengths[j] + 1;
+                predecessors[i] = Some(j);
+            }
+        }
+    }
+    let mut subsequence = Vec::new();
+    let mut index = (0..values.len()).max_by_key(|i| (lengths[*i], std::cmp::Reverse(*i)));
+    while let Some(i) = index {
+        subsequence.push(values[i]);
+        index = predecessors[i];
+    }
+    subsequence.reverse();
+    subsequence
+}
+
+fn looks_alike(old_field: &msg::Field, new_field: &msg::Field) -> bool {
+    old_field.field_type() == new_field.field_type()
+        && old_field.base_type() == new_field.base_type()
+        && old_field.array_size() == new_field.array_size()
+        && roundtrip::optional_msg_value(old_field.initial_value())
+            == roundtrip::optional_msg_value(new_field.initial_value())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::business::msg_converter::msg_reader;
+
+    fn diff_str(old: &str, new: &str) -> Vec<Change> {
+        let old = msg_reader::parse("Msg".to_string(), old).unwrap();
+        let new = msg_reader::parse("Msg".to_string(), new).unwrap();
+        diff(&old, &new)
+    }
+
+    fn moved(name: &str, from: usize, to: usize) -> Change {
+        Change::Moved {
+            name: name.to_string(),
+            from,
+            to,
+        }
+    }
+
+    #[test]
+    fn reports_added_and_removed_fields() {
+        assert_eq!(
+            diff_str("int32 a\nint32 b\n", "int32 a\nfloat64 c\n"),
+            vec![
+                Change::Removed {
+                    name: "b".to_string()
+                },
+                Change::Added {
+                    name: "c".to_string()
+                }
+            ]
+        );
+        assert_eq!(diff_str("int32 a\n", "int32 a\n"), vec![]);
+    }
+
+    #[test]
+    fn reports_a_unique_look_alike_as_renamed() {
+        assert_eq!(
+            diff_str("int32 a\nstring b\n", "int32 a\nstring c\n"),
+            vec![Change::Renamed {
+                from: "b".to_string(),
+                to: "c".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn does_not_guess_between_several_look_alikes() {
+        let changes = diff_str("int32 a\nint32 b\n", "int32 c\nint32 d\n");
+        assert_eq!(changes.len(), 4);
+        assert!(!changes
+            .iter()
+            .any(|change| matches!(change, Change::Renamed { .. })));
+        assert_eq!(
+            diff_str("int32 a\nint32 b\n", "int32 a\nint32 c\nint32 d\n").len(),
+            3
+        );
+    }
+
+    #[test]
+    fn reports_a_single_move_once() {
+        assert_eq!(
+            diff_str(
+                "int32 a\nint32 b\nint32 c\nint32 d\n",
+                "int32 b\nint32 c\nint32 d\nint32 a\n"
+            ),
+            vec![moved("a", 0, 3)]
+        );
+        assert_eq!(
+            diff_str(
+                "int32 a\nint32 b\nint32 c\nint32 d\n",
+                "int32 d\nint32 a\nint32 b\nint32 c\n"
+            ),
+            vec![moved("d", 3, 0)]
+        );
+        assert_eq!(
+            diff_str("int32 a\nint32 b\nint32 c\n", "int32 x\nint32 b\nint32 c\n")
+                .into_iter()
+                .filter(|change| matches!(change, Change::Moved { .. }))
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn reports_changed_types_array_sizes_and_initial_values() {
+        assert_eq!(
+            diff_str(
+                "int32 a\nint32[2] b\nint32 c 1\nint32 D=1\n",
+                "int64 a\nint32[<=2] b\nint32 c 0x1\nint32 D 1\n"
+            ),
+            vec![
+                Change::BaseTypeChanged {
+                    name: "a".to_string(),
+                    from: msg::BaseType::Int32,
+                    to: msg::BaseType::Int64
+                },
+                Change::ArraySizeChanged {
+                    name: "b".to_string(),
+                    from: Some(msg::ArraySize::Capacity(2)),
+                    to: Some(msg::ArraySize::BoundDynamic(2))
+                },
+                Change::FieldTypeChanged {
+                    name: "D".to_string(),
+                    from: msg::FieldType::Constant,
+                    to: msg::FieldType::Variable
+                }
+            ]
+        );
+        assert!(matches!(
+            &diff_str("int32 c 1\n", "int32 c 2\n")[..],
+            [Change::InitialValueChanged { name, from: Some(_), to: Some(_) }] if name == "c"
+        ));
+    }
+}
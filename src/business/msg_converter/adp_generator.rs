@@ -0,0 +1,56 @@
+use crate::business::msg_converter::fbt_generator::{create_event, create_primitive, create_var};
+use crate::core::{adp, dtp, fbt};
+
+const PLUG_INTERFACE: &str = "PLUG";
+const SOCKET_INTERFACE: &str = "SOCKET";
+
+// the interface is seen from the plug, i.e. the service client
+pub fn generate(
+    name: String,
+    request: &dtp::DataType,
+    response: &dtp::DataType,
+) -> adp::AdapterType {
+    let interface_list = fbt::InterfaceList::new(
+        vec![create_event(
+            "CNF",
+            "Response of the service server",
+            &["RESPONSE"],
+        )],
+        vec![create_event(
+            "REQ",
+            "Request to the service server",
+            &["REQUEST"],
+        )],
+        vec![create_var(
+            "RESPONSE",
+            dtp::BaseType::Custom(response.type_name()),
+            "Service response",
+        )],
+        vec![create_var(
+            "REQUEST",
+            dtp::BaseType::Custom(request.type_name()),
+            "Service request",
+        )],
+    );
+    let service = fbt::Service::new(
+        PLUG_INTERFACE.to_string(),
+        SOCKET_INTERFACE.to_string(),
+        vec![fbt::ServiceSequence::new(
+            "request_response".to_string(),
+            vec![fbt::ServiceTransaction::new(
+                Some(create_primitive(PLUG_INTERFACE, "REQ", Some("REQUEST"))),
+                vec![
+                    create_primitive(SOCKET_INTERFACE, "REQ", Some("REQUEST")),
+                    create_primitive(SOCKET_INTERFACE, "CNF", Some("RESPONSE")),
+                    create_primitive(PLUG_INTERFACE, "CNF", Some("RESPONSE")),
+                ],
+            )],
+        )],
+    );
+    adp::AdapterType::new(
+        name,
+        Some("Connects a ROS 2 service client and server".to_string()),
+        interface_list,
+        service,
+    )
+}
@@ -0,0 +1,57 @@
+use std::borrow::Cow;
+
+use xmltree::{Element, EmitterConfig, XMLNode};
+
+use crate::business::error::Result;
+use crate::business::file_writer;
+use crate::business::msg_converter::fbt_writer::{
+    create_interface_list_element, create_service_element,
+};
+use crate::core::adp::*;
+use crate::core::dtp::{
+    XML_ATTRIBUTE_COMMENT, XML_ATTRIBUTE_NAME, XML_ATTRIBUTE_STANDARD, XML_TAG_IDENTIFICATION,
+};
+
+/// Returns the path to the written file
+pub fn write(adapter_type: &AdapterType, to_directory: &str) -> Result<String> {
+    let adapter_type_element = create_adapter_type_element(adapter_type);
+
+    let mut config = EmitterConfig::new();
+    config.perform_escaping = false;
+    config.perform_indent = true;
+    config.indent_string = Cow::Borrowed("    ");
+    config.pad_self_closing = false;
+    let mut content = Vec::new();
+    adapter_type_element.write_with_config(&mut content, config)?;
+    let path_to_file = format!("{to_directory}{}.adp", adapter_type.name());
+    file_writer::write_if_changed(&path_to_file, content)?;
+    Ok(path_to_file)
+}
+
+fn create_adapter_type_element(adapter_type: &AdapterType) -> Element {
+    let mut adapter_type_element = Element::new(XML_TAG_ADAPTER_TYPE);
+    adapter_type_element.attributes.insert(
+        XML_ATTRIBUTE_NAME.to_string(),
+        adapter_type.name().to_string(),
+    );
+    if let Some(comment) = adapter_type.comment() {
+        adapter_type_element
+            .attributes
+            .insert(XML_ATTRIBUTE_COMMENT.to_string(), comment.clone());
+    }
+    let mut identification_element = Element::new(XML_TAG_IDENTIFICATION);
+    identification_element.attributes.insert(
+        XML_ATTRIBUTE_STANDARD.to_string(),
+        XML_VALUE_ADAPTER_STANDARD.to_string(),
+    );
+    adapter_type_element
+        .children
+        .push(XMLNode::Element(identification_element));
+    adapter_type_element
+        .children
+        .push(create_interface_list_element(adapter_type.interface_list()));
+    adapter_type_element
+        .children
+        .push(create_service_element(adapter_type.service()));
+    adapter_type_element
+}
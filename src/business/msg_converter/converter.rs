@@ -1,53 +1,174 @@
 use crate::business::error::Result;
+use crate::business::naming;
+use crate::business::validation::MAX_PADDED_ARRAY_LENGTH;
 use crate::core::dtp::{
     ANNOTATION_NAME_ROS2_ABSOLUTE_REFERENCE, ANNOTATION_NAME_ROS2_BOUND_DYNAMIC_ARRAY,
-    ANNOTATION_NAME_ROS2_CONSTANT, ANNOTATION_NAME_ROS2_DYNAMIC_ARRAY,
-    ANNOTATION_NAME_ROS2_ELEMENT_COUNTER, ANNOTATION_NAME_ROS2_RELATIVE_REFERENCE,
+    ANNOTATION_NAME_ROS2_CONSTANT, ANNOTATION_NAME_ROS2_DEFAULT, ANNOTATION_NAME_ROS2_DYNAMIC_ARRAY,
+    ANNOTATION_NAME_ROS2_ELEMENT_COUNTER, ANNOTATION_NAME_ROS2_KEY, ANNOTATION_NAME_ROS2_NAME,
+    ANNOTATION_NAME_ROS2_RANGE_MAX, ANNOTATION_NAME_ROS2_RANGE_MIN,
+    ANNOTATION_NAME_ROS2_RELATIVE_REFERENCE, ANNOTATION_NAME_ROS2_UNIT,
+    ANNOTATION_NAME_ROS2_VERBATIM, PACKAGE_NAME_ROS2, PACKAGE_SEPARATOR,
+    XML_VALUE_DATA_TYPE_STANDARD,
 };
 use crate::core::msg::{
     ANNOTATION_NAME_IEC61499_WORD, ANNOTATION_NAME_IEC61499_DWORD, 
     ANNOTATION_NAME_IEC61499_LWORD, ANNOTATION_NAME_IEC61499_START_INDEX, 
+    ANNOTATION_NAME_IEC61499_NAME,
 };
 use crate::core::{dtp, msg};
 use nom::branch::alt;
-use nom::bytes::complete::tag;
+use nom::bytes::complete::{tag, take_while1};
 use nom::character::complete::digit1;
-use nom::combinator::{map_res, opt, recognize};
+use nom::combinator::{map, map_res, opt, recognize};
 use nom::sequence::{delimited, tuple};
 use nom::Finish;
 
 const ELEMENT_COUNTER_SUFFIX: &str = "_element_counter";
+const MESSAGE_INTERFACE_TYPE: &str = "msg";
+const SERVICE_INTERFACE_TYPE: &str = "srv";
+
+/// How the ROS 2 package of an interface is encoded into the name of the converted type
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TypeNaming {
+    /// Prefix the name, e.g. "ROS2_geometrymsgs_msg_Point", for 4diac IDE versions without packages
+    Mangled,
+    /// Declare a package, e.g. "ros2::geometry_msgs::Point", as supported since 4diac IDE 3.0
+    Packaged,
+}
+
+pub fn convert(
+    package_name: &str,
+    structured_type: &msg::StructuredType,
+    version_info: &dtp::VersionInfo,
+    type_naming: TypeNaming,
+) -> Result<dtp::DataType> {
+    convert_interface(
+        package_name,
+        MESSAGE_INTERFACE_TYPE,
+        structured_type,
+        version_info,
+        type_naming,
+    )
+}
 
-pub fn convert(package_name: &str, structured_type: &msg::StructuredType) -> Result<dtp::DataType> {
-    let name = convert_structured_type_name(package_name, structured_type.name());
+pub fn convert_service(
+    package_name: &str,
+    service: &msg::Service,
+    version_info: &dtp::VersionInfo,
+    type_naming: TypeNaming,
+) -> Result<(dtp::DataType, dtp::DataType)> {
+    Ok((
+        convert_interface(
+            package_name,
+            SERVICE_INTERFACE_TYPE,
+            service.request(),
+            version_info,
+            type_naming,
+        )?,
+        convert_interface(
+            package_name,
+            SERVICE_INTERFACE_TYPE,
+            service.response(),
+            version_info,
+            type_naming,
+        )?,
+    ))
+}
+
+pub fn convert_service_name(
+    package_name: &str,
+    service: &msg::Service,
+    type_naming: TypeNaming,
+) -> String {
+    convert_structured_type_name(
+        package_name,
+        SERVICE_INTERFACE_TYPE,
+        service.name(),
+        type_naming,
+    )
+    .name()
+    .to_string()
+}
+
+fn convert_interface(
+    package_name: &str,
+    interface_type: &str,
+    structured_type: &msg::StructuredType,
+    version_info: &dtp::VersionInfo,
+    type_naming: TypeNaming,
+) -> Result<dtp::DataType> {
+    let type_name = convert_structured_type_name(
+        package_name,
+        interface_type,
+        structured_type.name(),
+        type_naming,
+    );
     let mut structured_type_children = Vec::new();
     for field in structured_type.fields().iter() {
-        let children = &mut convert_field(package_name, field)?;
+        let children = &mut convert_field(package_name, field, type_naming)?;
         structured_type_children.append(children)
     }
     let structured_type = dtp::StructuredType::new(None, structured_type_children);
-    Ok(dtp::DataType::new(name, None, structured_type))
-}
-
-fn convert_structured_type_name(package_name: &str, structured_type_name: &str) -> String {
-    let package_name = package_name
-        .replace("_", "")
-        .replace(" ", "")
-        .replace("-", "");
-    format!("ROS2_{package_name}_msg_{structured_type_name}")
+    let identification = dtp::Identification::new(
+        Some(XML_VALUE_DATA_TYPE_STANDARD.to_string()),
+        None,
+        None,
+        None,
+        None,
+        None,
+    );
+    let compiler_info = type_name
+        .package()
+        .map(|package| dtp::CompilerInfo::new(Some(package.clone()), None, None, Vec::new()));
+    Ok(dtp::DataType::new(
+        type_name.name().to_string(),
+        None,
+        Some(identification),
+        vec![version_info.clone()],
+        compiler_info,
+        structured_type,
+    ))
+}
+
+fn convert_structured_type_name(
+    package_name: &str,
+    interface_type: &str,
+    structured_type_name: &str,
+    type_naming: TypeNaming,
+) -> dtp::TypeName {
+    match type_naming {
+        TypeNaming::Mangled => {
+            let package_name = package_name
+                .replace("_", "")
+                .replace(" ", "")
+                .replace("-", "");
+            dtp::TypeName::new(
+                None,
+                format!("ROS2_{package_name}_{interface_type}_{structured_type_name}"),
+            )
+        }
+        TypeNaming::Packaged => dtp::TypeName::new(
+            Some(format!("{PACKAGE_NAME_ROS2}{PACKAGE_SEPARATOR}{package_name}")),
+            structured_type_name.to_string(),
+        ),
+    }
 }
 
-fn convert_field(package_name: &str, field: &msg::Field) -> Result<Vec<dtp::VarDeclaration>> {
+fn convert_field(
+    package_name: &str,
+    field: &msg::Field,
+    type_naming: TypeNaming,
+) -> Result<Vec<dtp::VarDeclaration>> {
     let mut var_declarations = Vec::new();
 
     let var_name = convert_to_var_name(field)?;
     var_declarations.push(dtp::VarDeclaration::new(
         var_name.clone(),
-        convert_to_var_base_type(package_name, field),
+        convert_to_var_base_type(package_name, field, type_naming),
         convert_to_dtp_optional_array_size(field)?,
         convert_to_dtp_optional_initial_value(field)?,
         convert_to_var_comment(field)?,
-        convert_to_attributes(field)?,
+        convert_to_attributes(package_name, field, type_naming)?,
     ));
 
     if let Some(msg::ArraySize::BoundDynamic(_)) | Some(msg::ArraySize::Dynamic) =
@@ -66,8 +187,7 @@ fn convert_field(package_name: &str, field: &msg::Field) -> Result<Vec<dtp::VarD
                 ANNOTATION_NAME_ROS2_ELEMENT_COUNTER.to_owned(),
                 dtp::BaseType::STRING(None),
                 dtp::InitialValue::STRING(
-                    field
-                        .name()
+                    var_name
                         .chars()
                         .map(dtp::CharRepresentation::Char)
                         .collect::<Vec<_>>(),
@@ -81,10 +201,14 @@ fn convert_field(package_name: &str, field: &msg::Field) -> Result<Vec<dtp::VarD
 }
 
 fn convert_to_var_name(field: &msg::Field) -> Result<String> {
-    Ok(field.name().to_string())
+    Ok(get_original_name(field)?.unwrap_or_else(|| naming::escape_iec61131_keyword(field.name())))
 }
 
-fn convert_to_var_base_type(package_name: &str, field: &msg::Field) -> dtp::BaseType {
+fn convert_to_var_base_type(
+    package_name: &str,
+    field: &msg::Field,
+    type_naming: TypeNaming,
+) -> dtp::BaseType {
     match field.base_type() {
         msg::BaseType::Bool => dtp::BaseType::BOOL,
         msg::BaseType::Byte => dtp::BaseType::BYTE,
@@ -105,7 +229,7 @@ fn convert_to_var_base_type(package_name: &str, field: &msg::Field) -> dtp::Base
         msg::BaseType::String(opt_bound) => dtp::BaseType::STRING(*opt_bound),
         msg::BaseType::Wstring(opt_bound) => dtp::BaseType::WSTRING(*opt_bound),
         msg::BaseType::Custom(reference) => {
-            dtp::BaseType::Custom(convert_reference(package_name, reference))
+            dtp::BaseType::Custom(convert_reference(package_name, reference, type_naming))
         }
     }
 }
@@ -144,7 +268,11 @@ fn compute_element_counter_default_count(field: &msg::Field) -> u64 {
     }
 }
 
-fn convert_to_attributes(field: &msg::Field) -> Result<Vec<dtp::Attribute>> {
+fn convert_to_attributes(
+    package_name: &str,
+    field: &msg::Field,
+    type_naming: TypeNaming,
+) -> Result<Vec<dtp::Attribute>> {
     let mut attributes = Vec::new();
     if let msg::BaseType::Custom(msg::Reference::Relative { .. }) = field.base_type() {
         attributes.push(dtp::Attribute::new(
@@ -186,6 +314,88 @@ fn convert_to_attributes(field: &msg::Field) -> Result<Vec<dtp::Attribute>> {
             None,
         ))
     }
+    if get_original_name(field)?.is_none() && naming::is_iec61131_keyword(field.name()) {
+        attributes.push(dtp::Attribute::new(
+            ANNOTATION_NAME_ROS2_NAME.to_owned(),
+            dtp::BaseType::STRING(None),
+            dtp::InitialValue::STRING(
+                field
+                    .name()
+                    .chars()
+                    .map(dtp::CharRepresentation::Char)
+                    .collect::<Vec<_>>(),
+            ),
+            None,
+        ))
+    }
+    for annotation in field.annotations() {
+        attributes.append(&mut convert_annotation(
+            package_name,
+            field,
+            annotation,
+            type_naming,
+        )?);
+    }
+    Ok(attributes)
+}
+
+fn convert_annotation(
+    package_name: &str,
+    field: &msg::Field,
+    annotation: &msg::Annotation,
+    type_naming: TypeNaming,
+) -> Result<Vec<dtp::Attribute>> {
+    let mut attributes = Vec::new();
+    match annotation {
+        msg::Annotation::Key => attributes.push(dtp::Attribute::new(
+            ANNOTATION_NAME_ROS2_KEY.to_owned(),
+            dtp::BaseType::BOOL,
+            dtp::InitialValue::BOOL(dtp::BoolRepresentation::Binary(true)),
+            None,
+        )),
+        msg::Annotation::Unit(unit) => attributes.push(dtp::Attribute::new(
+            ANNOTATION_NAME_ROS2_UNIT.to_owned(),
+            dtp::BaseType::STRING(None),
+            dtp::InitialValue::STRING(
+                unit.chars()
+                    .map(dtp::CharRepresentation::Char)
+                    .collect::<Vec<_>>(),
+            ),
+            None,
+        )),
+        msg::Annotation::Range { min, max } => {
+            for (name, bound) in [
+                (ANNOTATION_NAME_ROS2_RANGE_MIN, min),
+                (ANNOTATION_NAME_ROS2_RANGE_MAX, max),
+            ] {
+                if let Some(bound) = bound {
+                    attributes.push(dtp::Attribute::new(
+                        name.to_owned(),
+                        convert_to_var_base_type(package_name, field, type_naming),
+                        convert_initial_value(bound, field)?,
+                        None,
+                    ));
+                }
+            }
+        }
+        msg::Annotation::Default(initial_value) => attributes.push(dtp::Attribute::new(
+            ANNOTATION_NAME_ROS2_DEFAULT.to_owned(),
+            convert_to_var_base_type(package_name, field, type_naming),
+            convert_initial_value(initial_value, field)?,
+            None,
+        )),
+        // the language of the verbatim text is kept as the comment of the attribute
+        msg::Annotation::Verbatim { language, text } => attributes.push(dtp::Attribute::new(
+            ANNOTATION_NAME_ROS2_VERBATIM.to_owned(),
+            dtp::BaseType::STRING(None),
+            dtp::InitialValue::STRING(
+                text.chars()
+                    .map(dtp::CharRepresentation::Char)
+                    .collect::<Vec<_>>(),
+            ),
+            language.clone(),
+        )),
+    }
     Ok(attributes)
 }
 
@@ -193,7 +403,15 @@ fn convert_to_dtp_optional_array_size(field: &msg::Field) -> Result<Option<dtp::
     Ok(match field.array_size() {
         Some(msg::ArraySize::Capacity(capacity)) if is_shifted_static_array(field) => {
             let start = get_start_index(field)?;
-            let end = start + (*capacity as i64) - 1;
+            let end = i64::try_from(*capacity)
+                .ok()
+                .and_then(|capacity| start.checked_add(capacity - 1))
+                .ok_or_else(|| {
+                    format!(
+                        "The array \"{}\" with {capacity} elements cannot start at index {start}",
+                        field.name()
+                    )
+                })?;
             Some(dtp::ArraySize::Indexation(start, end))
         }
         Some(msg::ArraySize::Capacity(capacity)) => Some(dtp::ArraySize::Capacity(*capacity)),
@@ -211,10 +429,18 @@ fn convert_to_dtp_optional_initial_value(field: &msg::Field) -> Result<Option<dt
     })
 }
 
-fn convert_reference(package_name: &str, reference: &msg::Reference) -> String {
+fn convert_reference(
+    package_name: &str,
+    reference: &msg::Reference,
+    type_naming: TypeNaming,
+) -> dtp::TypeName {
     match reference {
-        msg::Reference::Relative { file } => convert_structured_type_name(package_name, file),
-        msg::Reference::Absolute { package, file } => convert_structured_type_name(package, file),
+        msg::Reference::Relative { file } => {
+            convert_structured_type_name(package_name, MESSAGE_INTERFACE_TYPE, file, type_naming)
+        }
+        msg::Reference::Absolute { package, file } => {
+            convert_structured_type_name(package, MESSAGE_INTERFACE_TYPE, file, type_naming)
+        }
     }
 }
 
@@ -285,10 +511,18 @@ fn convert_initial_value(
                 .collect::<Result<Vec<_>>>()?;
 
             // Add filler values as needed
+            if new_capacity > MAX_PADDED_ARRAY_LENGTH {
+                return Err(format!(
+                    "The initial value of \"{}\" cannot be padded to {new_capacity} array elements, \
+                    at most {MAX_PADDED_ARRAY_LENGTH} are supported",
+                    field.name()
+                )
+                .into());
+            }
             if new_capacity > vec.len() as u64 {
                 let sample_initial_value = v.iter().next();
                 vec.extend(vec![
-                    create_filler_initial_value(field, sample_initial_value);
+                    create_filler_initial_value(field, sample_initial_value)?;
                     new_capacity as usize - vec.len()
                 ]);
             }
@@ -301,9 +535,16 @@ fn convert_initial_value(
 fn create_filler_initial_value(
     field: &msg::Field,
     sample_initial_value: Option<&msg::InitialValue>,
-) -> dtp::InitialValue {
-    sample_initial_value.map_or_else(
-        || match field.base_type() {
+) -> Result<dtp::InitialValue> {
+    let no_filler = || -> Result<dtp::InitialValue> {
+        Err(format!(
+            "No default value can be derived for the elements of \"{}\"",
+            field.name()
+        )
+        .into())
+    };
+    Ok(match sample_initial_value {
+        None => match field.base_type() {
             msg::BaseType::Bool => dtp::InitialValue::BOOL(create_default_bool_representation()),
             msg::BaseType::Byte => dtp::InitialValue::BYTE(create_default_int_representation()),
             msg::BaseType::Uint16 if is_word(field) => dtp::InitialValue::WORD(create_default_int_representation()),
@@ -322,9 +563,9 @@ fn create_filler_initial_value(
             msg::BaseType::Char => dtp::InitialValue::CHAR(create_default_char_representation()),
             msg::BaseType::String(_) => dtp::InitialValue::STRING(create_default_string_representation()),
             msg::BaseType::Wstring(_) => dtp::InitialValue::WSTRING(create_default_wstring_representation()),
-            msg::BaseType::Custom(_) => unimplemented!(),
+            msg::BaseType::Custom(_) => return no_filler(),
         },
-        |sample_initial_value| match sample_initial_value {
+        Some(sample_initial_value) => match sample_initial_value {
             msg::InitialValue::Bool(reference) => dtp::InitialValue::BOOL(create_default_bool_representation_from_reference(reference)),
             msg::InitialValue::Byte(reference) => dtp::InitialValue::BYTE(create_default_int_representation_from_reference(reference)),
             msg::InitialValue::Uint16(reference) if is_word(field) => dtp::InitialValue::WORD(create_default_int_representation_from_reference(reference)),
@@ -343,9 +584,9 @@ fn create_filler_initial_value(
             msg::InitialValue::Char(_) => dtp::InitialValue::CHAR(create_default_char_representation()),
             msg::InitialValue::String(_) => dtp::InitialValue::STRING(create_default_string_representation()),
             msg::InitialValue::Wstring(_) => dtp::InitialValue::WSTRING(create_default_wstring_representation()),
-            msg::InitialValue::Array(_) => unimplemented!(),
+            msg::InitialValue::Array(_) => return no_filler(),
         },
-    )
+    })
 }
 
 
@@ -458,6 +699,32 @@ fn parse_start_index(input: &str) -> Result<i64> {
     .1)
 }
 
+fn get_original_name(field: &msg::Field) -> Result<Option<String>> {
+    field
+        .comment()
+        .and_then(|comment| {
+            comment
+                .find(format!("@{ANNOTATION_NAME_IEC61499_NAME}(").as_str())
+                .map(|pos| &comment[pos..])
+        })
+        .map(parse_original_name)
+        .transpose()
+}
+
+fn parse_original_name(input: &str) -> Result<String> {
+    Ok(map(
+        delimited(
+            tag(format!("@{ANNOTATION_NAME_IEC61499_NAME}(").as_str()),
+            take_while1(|c: char| c.is_ascii_alphanumeric() || c == '_'),
+            tag(")"),
+        ),
+        |str: &str| str.to_string(),
+    )(input)
+    .map_err(|e: nom::Err<nom::error::Error<&str>>| e.to_owned())
+    .finish()?
+    .1)
+}
+
 fn is_word(field: &msg::Field) -> bool {
     field
         .comment()
@@ -482,16 +749,17 @@ fn is_shifted_static_array(field: &msg::Field) -> bool {
         .is_some_and(|comment| comment.contains(format!("@{ANNOTATION_NAME_IEC61499_START_INDEX}").as_str()))
 }
 
+// A CHAR holds a single byte, like the char of ROS 2
 fn i64_to_char(value: &i64) -> Result<char> {
-    match char::from_u32(*value as u32) {
-        None => Err("The i64 is not a valid Unicode character.".into()),
+    match u8::try_from(*value).ok().map(char::from) {
+        None => Err(format!("The i64 {value} is not a character of a single byte.").into()),
         Some(c) => Ok(c),
     }
 }
 
 fn u64_to_char(value: &u64) -> Result<char> {
-    match char::from_u32(*value as u32) {
-        None => Err("The u64 is not a valid Unicode character.".into()),
+    match u8::try_from(*value).ok().map(char::from) {
+        None => Err(format!("The u64 {value} is not a character of a single byte.").into()),
         Some(c) => Ok(c),
     }
 }
@@ -1,13 +1,14 @@
 use std::borrow::Cow;
-use std::fs::File;
 use std::string::ToString;
 
 use xmltree::{Element, EmitterConfig, XMLNode};
 
 use crate::business::error::Result;
+use crate::business::file_writer;
 use crate::core::dtp::*;
 
-pub fn write(data_type: DataType, to_directory: &str) -> Result<()> {
+/// Returns the path to the written file
+pub fn write(data_type: DataType, to_directory: &str) -> Result<String> {
     let data_type_name = data_type.name().to_string();
     let custom_data_type_element = create_data_type_element(data_type);
 
@@ -16,9 +17,11 @@ pub fn write(data_type: DataType, to_directory: &str) -> Result<()> {
     config.perform_indent = true;
     config.indent_string = Cow::Borrowed("    ");
     config.pad_self_closing = false;
-    let file = File::create(format!("{to_directory}{data_type_name}.dtp"))?;
-    custom_data_type_element.write_with_config(file, config)?;
-    Ok(())
+    let mut content = Vec::new();
+    custom_data_type_element.write_with_config(&mut content, config)?;
+    let path_to_file = format!("{to_directory}{data_type_name}.dtp");
+    file_writer::write_if_changed(&path_to_file, content)?;
+    Ok(path_to_file)
 }
 
 fn create_data_type_element(data_type: DataType) -> Element {
@@ -27,9 +30,27 @@ fn create_data_type_element(data_type: DataType) -> Element {
         .attributes
         .insert(XML_ATTRIBUTE_NAME.to_string(), data_type.name().to_string());
     if let Some(comment) = data_type.comment() {
+        data_type_element.attributes.insert(
+            XML_ATTRIBUTE_COMMENT.to_string(),
+            mask_html_special_character(comment.clone()),
+        );
+    }
+    if let Some(identification) = data_type.identification() {
+        data_type_element
+            .children
+            .push(create_identification_element(identification));
+    }
+    data_type_element.children.append(
+        &mut data_type
+            .version_infos()
+            .iter()
+            .map(create_version_info_element)
+            .collect(),
+    );
+    if let Some(compiler_info) = data_type.compiler_info() {
         data_type_element
-            .attributes
-            .insert(XML_ATTRIBUTE_COMMENT.to_string(), comment.clone());
+            .children
+            .push(create_compiler_info_element(compiler_info));
     }
     data_type_element
         .children
@@ -37,12 +58,95 @@ fn create_data_type_element(data_type: DataType) -> Element {
     data_type_element
 }
 
+fn create_identification_element(identification: &Identification) -> XMLNode {
+    let mut identification_element = Element::new(XML_TAG_IDENTIFICATION);
+    insert_optional_attributes(
+        &mut identification_element,
+        [
+            (XML_ATTRIBUTE_STANDARD, identification.standard()),
+            (
+                XML_ATTRIBUTE_CLASSIFICATION,
+                identification.classification(),
+            ),
+            (
+                XML_ATTRIBUTE_APPLICATION_DOMAIN,
+                identification.application_domain(),
+            ),
+            (XML_ATTRIBUTE_FUNCTION, identification.function()),
+            (XML_ATTRIBUTE_TYPE, identification.identification_type()),
+            (XML_ATTRIBUTE_DESCRIPTION, identification.description()),
+        ],
+    );
+    XMLNode::Element(identification_element)
+}
+
+fn create_version_info_element(version_info: &VersionInfo) -> XMLNode {
+    let mut version_info_element = Element::new(XML_TAG_VERSION_INFO);
+    insert_optional_attributes(
+        &mut version_info_element,
+        [
+            (XML_ATTRIBUTE_ORGANIZATION, version_info.organization()),
+            (XML_ATTRIBUTE_VERSION, version_info.version()),
+            (XML_ATTRIBUTE_AUTHOR, version_info.author()),
+            (XML_ATTRIBUTE_DATE, version_info.date()),
+            (XML_ATTRIBUTE_REMARKS, version_info.remarks()),
+        ],
+    );
+    XMLNode::Element(version_info_element)
+}
+
+fn create_compiler_info_element(compiler_info: &CompilerInfo) -> XMLNode {
+    let mut compiler_info_element = Element::new(XML_TAG_COMPILER_INFO);
+    insert_optional_attributes(
+        &mut compiler_info_element,
+        [
+            (XML_ATTRIBUTE_PACKAGE_NAME, compiler_info.package_name()),
+            (XML_ATTRIBUTE_HEADER, compiler_info.header()),
+            (XML_ATTRIBUTE_CLASSDEF, compiler_info.classdef()),
+        ],
+    );
+    compiler_info_element.children.append(
+        &mut compiler_info
+            .compilers()
+            .iter()
+            .map(|compiler| {
+                let mut compiler_element = Element::new(XML_TAG_COMPILER);
+                insert_optional_attributes(
+                    &mut compiler_element,
+                    [
+                        (XML_ATTRIBUTE_LANGUAGE, compiler.language()),
+                        (XML_ATTRIBUTE_VENDOR, compiler.vendor()),
+                        (XML_ATTRIBUTE_PRODUCT, compiler.product()),
+                        (XML_ATTRIBUTE_VERSION, compiler.version()),
+                    ],
+                );
+                XMLNode::Element(compiler_element)
+            })
+            .collect(),
+    );
+    XMLNode::Element(compiler_info_element)
+}
+
+fn insert_optional_attributes<const N: usize>(
+    element: &mut Element,
+    attributes: [(&str, Option<&String>); N],
+) {
+    for (name, value) in attributes {
+        if let Some(value) = value {
+            element
+                .attributes
+                .insert(name.to_string(), mask_html_special_character(value.clone()));
+        }
+    }
+}
+
 fn create_structured_type_element(structured_type: &StructuredType) -> XMLNode {
     let mut structured_type_element = Element::new(XML_TAG_STRUCTURED_TYPE);
     if let Some(comment) = structured_type.comment() {
-        structured_type_element
-            .attributes
-            .insert(XML_ATTRIBUTE_COMMENT.to_string(), comment.clone());
+        structured_type_element.attributes.insert(
+            XML_ATTRIBUTE_COMMENT.to_string(),
+            mask_html_special_character(comment.clone()),
+        );
     }
     structured_type_element.children.append(
         &mut structured_type
@@ -54,7 +158,7 @@ fn create_structured_type_element(structured_type: &StructuredType) -> XMLNode {
     XMLNode::Element(structured_type_element)
 }
 
-fn create_var_declaration_element(var_declaration: &VarDeclaration) -> XMLNode {
+pub(super) fn create_var_declaration_element(var_declaration: &VarDeclaration) -> XMLNode {
     let mut var_declaration_element = Element::new(XML_TAG_VAR_DECLARATION);
     var_declaration_element.attributes.insert(
         XML_ATTRIBUTE_NAME.to_string(),
@@ -80,9 +184,10 @@ fn create_var_declaration_element(var_declaration: &VarDeclaration) -> XMLNode {
         );
     }
     if let Some(comment) = var_declaration.comment() {
-        var_declaration_element
-            .attributes
-            .insert(XML_ATTRIBUTE_COMMENT.to_string(), comment.clone());
+        var_declaration_element.attributes.insert(
+            XML_ATTRIBUTE_COMMENT.to_string(),
+            mask_html_special_character(comment.clone()),
+        );
     }
     var_declaration_element.children.append(
         &mut var_declaration
@@ -108,9 +213,10 @@ fn create_attribute_element(attribute: &Attribute) -> XMLNode {
         initial_value_to_string(attribute.value()),
     );
     if let Some(comment) = attribute.comment() {
-        attribute_element
-            .attributes
-            .insert(XML_ATTRIBUTE_COMMENT.to_owned(), comment.to_owned());
+        attribute_element.attributes.insert(
+            XML_ATTRIBUTE_COMMENT.to_owned(),
+            mask_html_special_character(comment.to_owned()),
+        );
     }
     XMLNode::Element(attribute_element)
 }
@@ -139,7 +245,7 @@ fn base_type_to_string(base_type: &BaseType) -> String {
         BaseType::WSTRING(opt_bound) => opt_bound
             .map(|bound| format!("WSTRING[{bound}]"))
             .unwrap_or_else(|| "WSTRING".to_string()),
-        BaseType::Custom(type_name) => type_name.clone(),
+        BaseType::Custom(type_name) => type_name.to_string(),
     }
 }
 
@@ -233,20 +339,31 @@ fn wstring_representation_as_string(wstring_representation: &[WcharRepresentatio
 
 fn char_reprensentation_as_string(char_representation: &CharRepresentation) -> String {
     match char_representation {
-        CharRepresentation::Char(char) if matches!(char, '$' | '\'') => format!("${char}"),
-        CharRepresentation::Char(char) => char.to_string(),
-        CharRepresentation::Hexadecimal(char) => format!("${:02X}", *char as u8),
+        CharRepresentation::Char(char) => mask_char(*char, '\'', 2),
+        CharRepresentation::Hexadecimal(byte) => format!("${byte:02X}"),
     }
 }
 
 fn wchar_representation_as_string(wchar_representation: &WcharRepresentation) -> String {
     match wchar_representation {
-        WcharRepresentation::Wchar(wchar) if matches!(wchar, '$' | '"') => format!("${wchar}"),
-        WcharRepresentation::Wchar(wchar) => wchar.to_string(),
+        WcharRepresentation::Wchar(wchar) => mask_char(*wchar, '"', 4),
         WcharRepresentation::Hexadecimal(wchar) => format!("${:04X}", *wchar as u32),
     }
 }
 
+fn mask_char(char: char, quote: char, hex_digits: usize) -> String {
+    match char {
+        '$' => "$$".to_string(),
+        '\n' => "$N".to_string(),
+        '\r' => "$R".to_string(),
+        '\t' => "$T".to_string(),
+        '\x0C' => "$P".to_string(),
+        char if char == quote => format!("${char}"),
+        char if char.is_control() => format!("${:0hex_digits$X}", char as u32),
+        char => char.to_string(),
+    }
+}
+
 fn mask_html_special_character(string: String) -> String {
     let mut masked = String::with_capacity(string.len());
     for c in string.chars() {
@@ -0,0 +1,181 @@
+use crate::core::{dtp, fbt};
+
+const PUBLISHER_SUFFIX: &str = "_PUBLISH";
+const SUBSCRIBER_SUFFIX: &str = "_SUBSCRIBE";
+const APPLICATION_INTERFACE: &str = "APPLICATION";
+const RESOURCE_INTERFACE: &str = "ROS2";
+
+pub fn generate(data_type: &dtp::DataType) -> Vec<fbt::FBType> {
+    vec![
+        generate_publisher(data_type),
+        generate_subscriber(data_type),
+    ]
+}
+
+fn generate_publisher(data_type: &dtp::DataType) -> fbt::FBType {
+    let interface_list = fbt::InterfaceList::new(
+        vec![
+            create_event("INIT", "Service Initialization", &["QI", "ID"]),
+            create_event("REQ", "Service Request", &["QI", "SD_1"]),
+        ],
+        vec![
+            create_event("INITO", "Initialization Confirm", &["QO", "STATUS"]),
+            create_event(
+                "CNF",
+                "Confirmation of Requested Service",
+                &["QO", "STATUS"],
+            ),
+        ],
+        vec![
+            create_var("QI", dtp::BaseType::BOOL, "Event Input Qualifier"),
+            create_var("ID", dtp::BaseType::WSTRING(None), "Topic name"),
+            create_var(
+                "SD_1",
+                dtp::BaseType::Custom(data_type.type_name()),
+                "Message to publish",
+            ),
+        ],
+        vec![
+            create_var("QO", dtp::BaseType::BOOL, "Event Output Qualifier"),
+            create_var("STATUS", dtp::BaseType::WSTRING(None), "Service Status"),
+        ],
+    );
+    let service = create_service(vec![
+        create_establishment_sequence(),
+        fbt::ServiceSequence::new(
+            "normal_publication".to_string(),
+            vec![fbt::ServiceTransaction::new(
+                Some(create_primitive(
+                    APPLICATION_INTERFACE,
+                    "REQ+",
+                    Some("SD_1"),
+                )),
+                vec![
+                    create_primitive(RESOURCE_INTERFACE, "publish", Some("SD_1")),
+                    create_primitive(APPLICATION_INTERFACE, "CNF+", None),
+                ],
+            )],
+        ),
+        create_release_sequence(),
+    ]);
+    fbt::FBType::new(
+        format!("{}{PUBLISHER_SUFFIX}", data_type.name()),
+        Some(format!("Publishes {} on a ROS 2 topic", data_type.name())),
+        interface_list,
+        service,
+    )
+}
+
+fn generate_subscriber(data_type: &dtp::DataType) -> fbt::FBType {
+    let interface_list = fbt::InterfaceList::new(
+        vec![create_event(
+            "INIT",
+            "Service Initialization",
+            &["QI", "ID"],
+        )],
+        vec![
+            create_event("INITO", "Initialization Confirm", &["QO", "STATUS"]),
+            create_event("IND", "Indication from Resource", &["QO", "STATUS", "RD_1"]),
+        ],
+        vec![
+            create_var("QI", dtp::BaseType::BOOL, "Event Input Qualifier"),
+            create_var("ID", dtp::BaseType::WSTRING(None), "Topic name"),
+        ],
+        vec![
+            create_var("QO", dtp::BaseType::BOOL, "Event Output Qualifier"),
+            create_var("STATUS", dtp::BaseType::WSTRING(None), "Service Status"),
+            create_var(
+                "RD_1",
+                dtp::BaseType::Custom(data_type.type_name()),
+                "Received message",
+            ),
+        ],
+    );
+    let service = create_service(vec![
+        create_establishment_sequence(),
+        fbt::ServiceSequence::new(
+            "normal_subscription".to_string(),
+            vec![fbt::ServiceTransaction::new(
+                Some(create_primitive(
+                    RESOURCE_INTERFACE,
+                    "message",
+                    Some("RD_1"),
+                )),
+                vec![create_primitive(
+                    APPLICATION_INTERFACE,
+                    "IND+",
+                    Some("RD_1"),
+                )],
+            )],
+        ),
+        create_release_sequence(),
+    ]);
+    fbt::FBType::new(
+        format!("{}{SUBSCRIBER_SUFFIX}", data_type.name()),
+        Some(format!(
+            "Subscribes to {} on a ROS 2 topic",
+            data_type.name()
+        )),
+        interface_list,
+        service,
+    )
+}
+
+pub(super) fn create_event(name: &str, comment: &str, with: &[&str]) -> fbt::Event {
+    fbt::Event::new(
+        name.to_string(),
+        Some(comment.to_string()),
+        with.iter().map(|var| var.to_string()).collect(),
+    )
+}
+
+pub(super) fn create_var(name: &str, base_type: dtp::BaseType, comment: &str) -> dtp::VarDeclaration {
+    dtp::VarDeclaration::new(
+        name.to_string(),
+        base_type,
+        None,
+        None,
+        Some(comment.to_string()),
+        Vec::new(),
+    )
+}
+
+fn create_service(sequences: Vec<fbt::ServiceSequence>) -> fbt::Service {
+    fbt::Service::new(
+        APPLICATION_INTERFACE.to_string(),
+        RESOURCE_INTERFACE.to_string(),
+        sequences,
+    )
+}
+
+fn create_establishment_sequence() -> fbt::ServiceSequence {
+    fbt::ServiceSequence::new(
+        "normal_establishment".to_string(),
+        vec![fbt::ServiceTransaction::new(
+            Some(create_primitive(APPLICATION_INTERFACE, "INIT+", Some("ID"))),
+            vec![create_primitive(APPLICATION_INTERFACE, "INITO+", None)],
+        )],
+    )
+}
+
+fn create_release_sequence() -> fbt::ServiceSequence {
+    fbt::ServiceSequence::new(
+        "normal_release".to_string(),
+        vec![fbt::ServiceTransaction::new(
+            Some(create_primitive(APPLICATION_INTERFACE, "INIT-", None)),
+            vec![create_primitive(APPLICATION_INTERFACE, "INITO-", None)],
+        )],
+    )
+}
+
+pub(super) fn create_primitive(
+    interface: &str,
+    event: &str,
+    parameters: Option<&str>,
+) -> fbt::ServicePrimitive {
+    fbt::ServicePrimitive::new(
+        interface.to_string(),
+        event.to_string(),
+        parameters.map(str::to_string),
+    )
+}
@@ -0,0 +1,198 @@
+use std::borrow::Cow;
+
+use xmltree::{Element, EmitterConfig, XMLNode};
+
+use crate::business::error::Result;
+use crate::business::file_writer;
+use crate::business::msg_converter::dtp_writer::create_var_declaration_element;
+use crate::core::dtp::{
+    VarDeclaration, XML_ATTRIBUTE_COMMENT, XML_ATTRIBUTE_NAME, XML_ATTRIBUTE_STANDARD,
+    XML_ATTRIBUTE_TYPE, XML_TAG_IDENTIFICATION,
+};
+use crate::core::fbt::*;
+
+/// Returns the path to the written file
+pub fn write(fb_type: &FBType, to_directory: &str) -> Result<String> {
+    let fb_type_element = create_fb_type_element(fb_type);
+
+    let mut config = EmitterConfig::new();
+    config.perform_escaping = false;
+    config.perform_indent = true;
+    config.indent_string = Cow::Borrowed("    ");
+    config.pad_self_closing = false;
+    let mut content = Vec::new();
+    fb_type_element.write_with_config(&mut content, config)?;
+    let path_to_file = format!("{to_directory}{}.fbt", fb_type.name());
+    file_writer::write_if_changed(&path_to_file, content)?;
+    Ok(path_to_file)
+}
+
+fn create_fb_type_element(fb_type: &FBType) -> Element {
+    let mut fb_type_element = Element::new(XML_TAG_FB_TYPE);
+    fb_type_element
+        .attributes
+        .insert(XML_ATTRIBUTE_NAME.to_string(), fb_type.name().to_string());
+    if let Some(comment) = fb_type.comment() {
+        fb_type_element
+            .attributes
+            .insert(XML_ATTRIBUTE_COMMENT.to_string(), comment.clone());
+    }
+    let mut identification_element = Element::new(XML_TAG_IDENTIFICATION);
+    identification_element.attributes.insert(
+        XML_ATTRIBUTE_STANDARD.to_string(),
+        XML_VALUE_SIFB_STANDARD.to_string(),
+    );
+    fb_type_element
+        .children
+        .push(XMLNode::Element(identification_element));
+    fb_type_element
+        .children
+        .push(create_interface_list_element(fb_type.interface_list()));
+    fb_type_element
+        .children
+        .push(create_service_element(fb_type.service()));
+    fb_type_element
+}
+
+pub(super) fn create_interface_list_element(interface_list: &InterfaceList) -> XMLNode {
+    let mut interface_list_element = Element::new(XML_TAG_INTERFACE_LIST);
+    interface_list_element.children.push(create_events_element(
+        XML_TAG_EVENT_INPUTS,
+        interface_list.event_inputs(),
+    ));
+    interface_list_element.children.push(create_events_element(
+        XML_TAG_EVENT_OUTPUTS,
+        interface_list.event_outputs(),
+    ));
+    interface_list_element.children.push(create_vars_element(
+        XML_TAG_INPUT_VARS,
+        interface_list.input_vars(),
+    ));
+    interface_list_element.children.push(create_vars_element(
+        XML_TAG_OUTPUT_VARS,
+        interface_list.output_vars(),
+    ));
+    XMLNode::Element(interface_list_element)
+}
+
+fn create_events_element(tag: &str, events: &[Event]) -> XMLNode {
+    let mut events_element = Element::new(tag);
+    events_element
+        .children
+        .append(&mut events.iter().map(create_event_element).collect());
+    XMLNode::Element(events_element)
+}
+
+fn create_event_element(event: &Event) -> XMLNode {
+    let mut event_element = Element::new(XML_TAG_EVENT);
+    event_element
+        .attributes
+        .insert(XML_ATTRIBUTE_NAME.to_string(), event.name().to_string());
+    event_element.attributes.insert(
+        XML_ATTRIBUTE_TYPE.to_string(),
+        XML_VALUE_EVENT_TYPE.to_string(),
+    );
+    if let Some(comment) = event.comment() {
+        event_element
+            .attributes
+            .insert(XML_ATTRIBUTE_COMMENT.to_string(), comment.clone());
+    }
+    event_element.children.append(
+        &mut event
+            .with()
+            .iter()
+            .map(|var| {
+                let mut with_element = Element::new(XML_TAG_WITH);
+                with_element
+                    .attributes
+                    .insert(XML_ATTRIBUTE_VAR.to_string(), var.clone());
+                XMLNode::Element(with_element)
+            })
+            .collect(),
+    );
+    XMLNode::Element(event_element)
+}
+
+fn create_vars_element(tag: &str, vars: &[VarDeclaration]) -> XMLNode {
+    let mut vars_element = Element::new(tag);
+    vars_element
+        .children
+        .append(&mut vars.iter().map(create_var_declaration_element).collect());
+    XMLNode::Element(vars_element)
+}
+
+pub(super) fn create_service_element(service: &Service) -> XMLNode {
+    let mut service_element = Element::new(XML_TAG_SERVICE);
+    service_element.attributes.insert(
+        XML_ATTRIBUTE_RIGHT_INTERFACE.to_string(),
+        service.right_interface().to_string(),
+    );
+    service_element.attributes.insert(
+        XML_ATTRIBUTE_LEFT_INTERFACE.to_string(),
+        service.left_interface().to_string(),
+    );
+    service_element.children.append(
+        &mut service
+            .sequences()
+            .iter()
+            .map(create_service_sequence_element)
+            .collect(),
+    );
+    XMLNode::Element(service_element)
+}
+
+fn create_service_sequence_element(service_sequence: &ServiceSequence) -> XMLNode {
+    let mut service_sequence_element = Element::new(XML_TAG_SERVICE_SEQUENCE);
+    service_sequence_element.attributes.insert(
+        XML_ATTRIBUTE_NAME.to_string(),
+        service_sequence.name().to_string(),
+    );
+    service_sequence_element.children.append(
+        &mut service_sequence
+            .transactions()
+            .iter()
+            .map(create_service_transaction_element)
+            .collect(),
+    );
+    XMLNode::Element(service_sequence_element)
+}
+
+fn create_service_transaction_element(service_transaction: &ServiceTransaction) -> XMLNode {
+    let mut service_transaction_element = Element::new(XML_TAG_SERVICE_TRANSACTION);
+    if let Some(input_primitive) = service_transaction.input_primitive() {
+        service_transaction_element
+            .children
+            .push(create_primitive_element(
+                XML_TAG_INPUT_PRIMITIVE,
+                input_primitive,
+            ));
+    }
+    service_transaction_element.children.append(
+        &mut service_transaction
+            .output_primitives()
+            .iter()
+            .map(|output_primitive| {
+                create_primitive_element(XML_TAG_OUTPUT_PRIMITIVE, output_primitive)
+            })
+            .collect(),
+    );
+    XMLNode::Element(service_transaction_element)
+}
+
+fn create_primitive_element(tag: &str, service_primitive: &ServicePrimitive) -> XMLNode {
+    let mut primitive_element = Element::new(tag);
+    primitive_element.attributes.insert(
+        XML_ATTRIBUTE_INTERFACE.to_string(),
+        service_primitive.interface().to_string(),
+    );
+    primitive_element.attributes.insert(
+        XML_ATTRIBUTE_EVENT.to_string(),
+        service_primitive.event().to_string(),
+    );
+    if let Some(parameters) = service_primitive.parameters() {
+        primitive_element
+            .attributes
+            .insert(XML_ATTRIBUTE_PARAMETERS.to_string(), parameters.clone());
+    }
+    XMLNode::Element(primitive_element)
+}
@@ -0,0 +1,438 @@
+use std::collections::BTreeSet;
+
+use crate::business::dtp_converter::converter as dtp_converter;
+use crate::business::error::{Error, Result};
+use crate::business::file_writer;
+use crate::business::naming;
+use crate::core::dtp::{
+    self, ANNOTATION_NAME_ROS2_BOUND_DYNAMIC_ARRAY, ANNOTATION_NAME_ROS2_ELEMENT_COUNTER,
+};
+use crate::core::msg;
+
+const INDENTATION: &str = "  ";
+const MESSAGE_INTERFACE_TYPE: &str = "msg";
+const SERVICE_INTERFACE_TYPE: &str = "srv";
+const NAMESPACE: &str = "rossydiac";
+const SUPPORT_HEADER: &str = "rossydiac_forte_support.h";
+const SUPPORT_HEADER_CONTENT: &str = r#"// generated by RossyDiac
+// conversions between FORTE elementary data types and their rosidl C++ counterparts
+
+#pragma once
+
+#include <string>
+
+#include "forte_string.h"
+#include "forte_wstring.h"
+
+namespace rossydiac {
+
+template <typename TIec, typename TRos>
+inline void to_ros2(const TIec &source, TRos &target) {
+  target = static_cast<TRos>(source);
+}
+
+template <typename TRos, typename TIec>
+inline void from_ros2(const TRos &source, TIec &target) {
+  target = TIec(source);
+}
+
+inline void to_ros2(const CIEC_STRING &source, std::string &target) {
+  target.assign(source.getValue(), source.length());
+}
+
+inline void from_ros2(const std::string &source, CIEC_STRING &target) {
+  target.assign(source.c_str(), static_cast<TForteUInt16>(source.length()));
+}
+
+// FORTE stores WSTRING values UTF-8 encoded, rosidl uses UTF-16
+inline void to_ros2(const CIEC_WSTRING &source, std::u16string &target) {
+  target.clear();
+  const std::string utf8(source.getValue(), source.length());
+  for (std::size_t i = 0; i < utf8.size();) {
+    const unsigned char lead = static_cast<unsigned char>(utf8[i]);
+    const std::size_t length = lead < 0x80 ? 1 : lead < 0xE0 ? 2 : lead < 0xF0 ? 3 : 4;
+    char32_t code_point = length == 1 ? lead : lead & (0x3F >> (length - 1));
+    for (std::size_t j = 1; j < length && i + j < utf8.size(); ++j) {
+      code_point = (code_point << 6) | (static_cast<unsigned char>(utf8[i + j]) & 0x3F);
+    }
+    if (code_point > 0xFFFF) {
+      code_point -= 0x10000;
+      target.push_back(static_cast<char16_t>(0xD800 + (code_point >> 10)));
+      target.push_back(static_cast<char16_t>(0xDC00 + (code_point & 0x3FF)));
+    } else {
+      target.push_back(static_cast<char16_t>(code_point));
+    }
+    i += length;
+  }
+}
+
+inline void from_ros2(const std::u16string &source, CIEC_WSTRING &target) {
+  std::string utf8;
+  for (std::size_t i = 0; i < source.size(); ++i) {
+    char32_t code_point = source[i];
+    if (code_point >= 0xD800 && code_point < 0xDC00 && i + 1 < source.size()) {
+      code_point = 0x10000 + ((code_point - 0xD800) << 10) + (source[++i] - 0xDC00);
+    }
+    if (code_point < 0x80) {
+      utf8.push_back(static_cast<char>(code_point));
+    } else if (code_point < 0x800) {
+      utf8.push_back(static_cast<char>(0xC0 | (code_point >> 6)));
+      utf8.push_back(static_cast<char>(0x80 | (code_point & 0x3F)));
+    } else if (code_point < 0x10000) {
+      utf8.push_back(static_cast<char>(0xE0 | (code_point >> 12)));
+      utf8.push_back(static_cast<char>(0x80 | ((code_point >> 6) & 0x3F)));
+      utf8.push_back(static_cast<char>(0x80 | (code_point & 0x3F)));
+    } else {
+      utf8.push_back(static_cast<char>(0xF0 | (code_point >> 18)));
+      utf8.push_back(static_cast<char>(0x80 | ((code_point >> 12) & 0x3F)));
+      utf8.push_back(static_cast<char>(0x80 | ((code_point >> 6) & 0x3F)));
+      utf8.push_back(static_cast<char>(0x80 | (code_point & 0x3F)));
+    }
+  }
+  target.fromUTF8(utf8.c_str(), static_cast<int>(utf8.size()), false);
+}
+
+}  // namespace rossydiac
+"#;
+
+const SEQUENCE_HEADER: &str = "rossydiac_forte_sequence.h";
+const SEQUENCE_HEADER_CONTENT: &str = r#"// generated by RossyDiac
+// conversions between the fixed size FORTE arrays standing in for ROS 2 sequences and the
+// sequences themselves, honouring the element counter and the bound of the converted data types
+
+#pragma once
+
+#include <algorithm>
+#include <cstddef>
+#include <type_traits>
+
+#include "forte_ulint.h"
+#include "rossydiac_forte_support.h"
+
+namespace rossydiac {
+
+// a bound of 0 stands for an unbounded sequence
+inline std::size_t sequence_length(TForteUInt64 requested, std::size_t capacity, std::size_t bound) {
+  const std::size_t length = static_cast<std::size_t>(std::min<TForteUInt64>(requested, capacity));
+  return bound == 0 ? length : std::min(length, bound);
+}
+
+// copies the elements announced by the element counter, truncated to the array capacity and the bound
+template <typename TIecArray, typename TSequence>
+inline void to_ros2_sequence(const TIecArray &source, const CIEC_ULINT &element_counter, TForteInt32 start,
+                             std::size_t capacity, std::size_t bound, TSequence &target) {
+  const std::size_t length = sequence_length(static_cast<TForteUInt64>(element_counter), capacity, bound);
+  target.resize(length);
+  for (std::size_t i = 0; i < length; ++i) {
+    to_ros2(source[start + static_cast<TForteInt32>(i)], target[i]);
+  }
+}
+
+// copies the sequence truncated to the array capacity and the bound, pads the remaining array
+// elements with their default value and updates the element counter
+template <typename TSequence, typename TIecArray>
+inline void from_ros2_sequence(const TSequence &source, TForteInt32 start, std::size_t capacity, std::size_t bound,
+                               TIecArray &target, CIEC_ULINT &element_counter) {
+  const std::size_t length = sequence_length(source.size(), capacity, bound);
+  for (std::size_t i = 0; i < length; ++i) {
+    from_ros2(source[i], target[start + static_cast<TForteInt32>(i)]);
+  }
+  for (std::size_t i = length; i < capacity; ++i) {
+    auto &element = target[start + static_cast<TForteInt32>(i)];
+    element = std::remove_reference_t<decltype(element)>();
+  }
+  element_counter = CIEC_ULINT(static_cast<TForteUInt64>(length));
+}
+
+}  // namespace rossydiac
+"#;
+
+// The ROS 2 interface file the converted type stems from
+struct Interface<'a> {
+    package_name: &'a str,
+    interface_type: &'static str,
+    file_name: &'a str,
+}
+
+struct Member<'a> {
+    field: &'a msg::Field,
+    var_declaration: &'a dtp::VarDeclaration,
+    element_counter: Option<&'a dtp::VarDeclaration>,
+}
+
+/// Returns the path to the written file
+pub fn write(
+    package_name: &str,
+    msg_dto: &msg::StructuredType,
+    dtp_dto: &dtp::DataType,
+    to_directory: &str,
+) -> Result<String> {
+    let interface = Interface {
+        package_name,
+        interface_type: MESSAGE_INTERFACE_TYPE,
+        file_name: msg_dto.name(),
+    };
+    write_interface(&interface, msg_dto, dtp_dto, to_directory)
+}
+
+// rosidl declares the request and response of a service in the header of the service
+/// Returns the paths to the written files of the request and the response
+pub fn write_service(
+    package_name: &str,
+    service_dto: &msg::Service,
+    request_dtp_dto: &dtp::DataType,
+    response_dtp_dto: &dtp::DataType,
+    to_directory: &str,
+) -> Result<[String; 2]> {
+    let interface = Interface {
+        package_name,
+        interface_type: SERVICE_INTERFACE_TYPE,
+        file_name: service_dto.name(),
+    };
+    Ok([
+        write_interface(&interface, service_dto.request(), request_dtp_dto, to_directory)?,
+        write_interface(&interface, service_dto.response(), response_dtp_dto, to_directory)?,
+    ])
+}
+
+fn write_interface(
+    interface: &Interface,
+    msg_dto: &msg::StructuredType,
+    dtp_dto: &dtp::DataType,
+    to_directory: &str,
+) -> Result<String> {
+    let members = pair_members(msg_dto, dtp_dto)?;
+    let path_to_file = format!("{to_directory}{}_ros2.h", dtp_dto.name());
+    file_writer::write_if_changed(
+        &path_to_file,
+        members_as_string(interface, msg_dto, dtp_dto, &members),
+    )?;
+    Ok(path_to_file)
+}
+
+/// Writes the headers all generated conversions include, once for all of them so that parallel
+/// conversions do not write the same files
+pub fn write_support_headers(to_directory: &str) -> Result<()> {
+    file_writer::write_if_changed(
+        &format!("{to_directory}{SUPPORT_HEADER}"),
+        SUPPORT_HEADER_CONTENT,
+    )?;
+    file_writer::write_if_changed(
+        &format!("{to_directory}{SEQUENCE_HEADER}"),
+        SEQUENCE_HEADER_CONTENT,
+    )?;
+    Ok(())
+}
+
+// every field was converted into one var declaration, dynamic arrays additionally into an element counter
+fn pair_members<'a>(
+    msg_dto: &'a msg::StructuredType,
+    dtp_dto: &'a dtp::DataType,
+) -> Result<Vec<Member<'a>>> {
+    let all_var_declarations = dtp_dto.structured_type().var_declarations();
+    let var_declarations: Vec<&dtp::VarDeclaration> = all_var_declarations
+        .iter()
+        .filter(|var_declaration| !is_element_counter(var_declaration))
+        .collect();
+    if var_declarations.len() != msg_dto.fields().len() {
+        return Err(Error::custom(format!(
+            "{} does not match the fields of {}",
+            dtp_dto.name(),
+            msg_dto.name()
+        )));
+    }
+    let mut members = Vec::new();
+    for (field, var_declaration) in msg_dto.fields().iter().zip(var_declarations) {
+        if *field.field_type() == msg::FieldType::Constant {
+            continue;
+        }
+        let element_counter = match field.array_size() {
+            Some(msg::ArraySize::Dynamic) | Some(msg::ArraySize::BoundDynamic(_)) => Some(
+                all_var_declarations
+                    .iter()
+                    .find(|helper| dtp_converter::is_element_counter_of(var_declaration, helper))
+                    .ok_or(format!(
+                        "No element counter found for \"{}\"",
+                        var_declaration.name()
+                    ))?,
+            ),
+            _ => None,
+        };
+        members.push(Member {
+            field,
+            var_declaration,
+            element_counter,
+        });
+    }
+    Ok(members)
+}
+
+fn is_element_counter(var_declaration: &dtp::VarDeclaration) -> bool {
+    var_declaration
+        .attributes()
+        .iter()
+        .any(|attribute| attribute.name() == ANNOTATION_NAME_ROS2_ELEMENT_COUNTER)
+}
+
+fn members_as_string(
+    interface: &Interface,
+    msg_dto: &msg::StructuredType,
+    dtp_dto: &dtp::DataType,
+    members: &[Member],
+) -> String {
+    let Interface {
+        package_name,
+        interface_type,
+        file_name,
+    } = interface;
+    let iec_type = format!("CIEC_{}", dtp_dto.name());
+    let ros2_type = format!("{package_name}::{interface_type}::{}", msg_dto.name());
+
+    let mut lines: Vec<String> = vec![
+        "// generated by RossyDiac".to_string(),
+        format!("// with input from {package_name}/{interface_type}/{file_name}"),
+        String::new(),
+        "#pragma once".to_string(),
+        String::new(),
+        "#include <cstddef>".to_string(),
+        String::new(),
+        format!("#include \"{}_dtp.h\"", dtp_dto.name()),
+        format!(
+            "#include \"{package_name}/{interface_type}/{}.hpp\"",
+            naming::to_ros2_header_name(file_name)
+        ),
+        format!("#include \"{SUPPORT_HEADER}\""),
+    ];
+    if members
+        .iter()
+        .any(|member| member.element_counter.is_some())
+    {
+        lines.push(format!("#include \"{SEQUENCE_HEADER}\""));
+    }
+    let includes: BTreeSet<String> = members
+        .iter()
+        .filter_map(|member| match member.var_declaration.base_type() {
+            dtp::BaseType::Custom(type_name) => {
+                Some(format!("#include \"{}_ros2.h\"", type_name.name()))
+            }
+            _ => None,
+        })
+        .collect();
+    lines.extend(includes);
+    lines.push(String::new());
+    lines.push(format!("namespace {NAMESPACE} {{"));
+    lines.push(String::new());
+
+    lines.push(format!(
+        "inline void to_ros2(const {iec_type} &source, {ros2_type} &target) {{"
+    ));
+    for member in members {
+        lines.extend(
+            to_ros2_as_lines(member)
+                .into_iter()
+                .map(|line| format!("{INDENTATION}{line}")),
+        );
+    }
+    lines.push("}".to_string());
+    lines.push(String::new());
+
+    lines.push(format!(
+        "inline void from_ros2(const {ros2_type} &source, {iec_type} &target) {{"
+    ));
+    for member in members {
+        lines.extend(
+            from_ros2_as_lines(member)
+                .into_iter()
+                .map(|line| format!("{INDENTATION}{line}")),
+        );
+    }
+    lines.push("}".to_string());
+    lines.push(String::new());
+    lines.push(format!("}}  // namespace {NAMESPACE}"));
+
+    let mut result = lines.join("\n");
+    result.push('\n');
+    result
+}
+
+fn to_ros2_as_lines(member: &Member) -> Vec<String> {
+    let iec_name = format!("var_{}", member.var_declaration.name());
+    let ros2_name = member.field.name();
+    let (start, capacity) = array_bounds(member.var_declaration);
+    match member.field.array_size() {
+        None => vec![format!("to_ros2(source.{iec_name}, target.{ros2_name});")],
+        Some(msg::ArraySize::Capacity(_)) => vec![
+            format!("for (std::size_t i = 0; i < {capacity}; ++i) {{"),
+            format!(
+                "{INDENTATION}to_ros2(source.{iec_name}[{}], target.{ros2_name}[i]);",
+                iec_index_as_string(start)
+            ),
+            "}".to_string(),
+        ],
+        Some(msg::ArraySize::Dynamic) | Some(msg::ArraySize::BoundDynamic(_)) => {
+            vec![format!(
+                "to_ros2_sequence(source.{iec_name}, source.var_{}, {start}, {capacity}, {}, target.{ros2_name});",
+                element_counter_name(member),
+                sequence_bound(member.var_declaration)
+            )]
+        }
+    }
+}
+
+fn from_ros2_as_lines(member: &Member) -> Vec<String> {
+    let iec_name = format!("var_{}", member.var_declaration.name());
+    let ros2_name = member.field.name();
+    let (start, capacity) = array_bounds(member.var_declaration);
+    match member.field.array_size() {
+        None => vec![format!("from_ros2(source.{ros2_name}, target.{iec_name});")],
+        Some(msg::ArraySize::Capacity(_)) => vec![
+            format!("for (std::size_t i = 0; i < {capacity}; ++i) {{"),
+            format!(
+                "{INDENTATION}from_ros2(source.{ros2_name}[i], target.{iec_name}[{}]);",
+                iec_index_as_string(start)
+            ),
+            "}".to_string(),
+        ],
+        Some(msg::ArraySize::Dynamic) | Some(msg::ArraySize::BoundDynamic(_)) => {
+            vec![format!(
+                "from_ros2_sequence(source.{ros2_name}, {start}, {capacity}, {}, target.{iec_name}, target.var_{});",
+                sequence_bound(member.var_declaration),
+                element_counter_name(member)
+            )]
+        }
+    }
+}
+
+fn element_counter_name<'a>(member: &Member<'a>) -> &'a str {
+    member
+        .element_counter
+        .map(|element_counter| element_counter.name())
+        .unwrap_or_default()
+}
+
+// 0 stands for an unbounded sequence, see sequence_length in the sequence header
+fn sequence_bound(var_declaration: &dtp::VarDeclaration) -> u64 {
+    var_declaration
+        .attributes()
+        .iter()
+        .filter(|attribute| attribute.name() == ANNOTATION_NAME_ROS2_BOUND_DYNAMIC_ARRAY)
+        .find_map(|attribute| dtp_converter::array_bound_from_intial_value(attribute.value()).ok())
+        .unwrap_or(0)
+}
+
+fn array_bounds(var_declaration: &dtp::VarDeclaration) -> (i64, u64) {
+    match var_declaration.array_size() {
+        Some(dtp::ArraySize::Capacity(capacity)) => (0, *capacity),
+        Some(dtp::ArraySize::Indexation(start, end)) => (*start, start.abs_diff(*end) + 1),
+        None => (0, 0),
+    }
+}
+
+// FORTE arrays are accessed with their IEC 61131-3 index
+fn iec_index_as_string(start: i64) -> String {
+    match start {
+        0 => "i".to_string(),
+        start if start < 0 => format!("static_cast<TForteInt32>(i) - {}", start.unsigned_abs()),
+        start => format!("static_cast<TForteInt32>(i) + {start}"),
+    }
+}
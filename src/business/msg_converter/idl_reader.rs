@@ -0,0 +1,1205 @@
+use std::collections::HashMap;
+
+use log::{debug, info};
+use nom::branch::alt;
+use nom::bytes::complete::{is_not, tag, take_until, take_while};
+use nom::character::complete::{char, digit0, digit1, multispace1, oct_digit1, one_of, satisfy};
+use nom::combinator::{all_consuming, map, map_res, not, opt, recognize, value};
+use nom::multi::{many0, many1, separated_list0, separated_list1};
+use nom::sequence::{delimited, pair, preceded, terminated, tuple};
+use nom::{Finish, IResult};
+
+use crate::business::error::Result;
+use crate::business::msg_converter::msg_reader::{parse_int_literal, parse_quoted_string};
+use crate::business::validation;
+use crate::core::msg::*;
+
+// rosidl adds this member to structures which would otherwise be empty
+const PLACEHOLDER_MEMBER_NAME: &str = "structure_needs_at_least_one_member";
+const CONSTANTS_MODULE_SUFFIX: &str = "_Constants";
+
+pub fn read(path_to_source_file: &str) -> Result<Vec<StructuredType>> {
+    info!("Start reading file {:?}", path_to_source_file);
+    let file_content = std::fs::read_to_string(path_to_source_file)?;
+    let structured_types = parse(&file_content)?;
+    info!("Finished reading file {:?}", path_to_source_file);
+    Ok(structured_types)
+}
+
+pub fn parse(content: &str) -> Result<Vec<StructuredType>> {
+    let definitions = parse_specification(content)
+        .map_err(|err| err.to_owned())
+        .finish()?
+        .1;
+    let structured_types = convert_definitions(&definitions)?;
+    for structured_type in &structured_types {
+        validation::validate_fields(structured_type.fields())?;
+    }
+    Ok(structured_types)
+}
+
+struct Definition {
+    annotations: Vec<AnnotationApplication>,
+    kind: DefinitionKind,
+}
+
+enum DefinitionKind {
+    Module {
+        name: String,
+        definitions: Vec<Definition>,
+    },
+    Struct {
+        name: String,
+        members: Vec<Member>,
+    },
+    Typedef {
+        type_spec: TypeSpec,
+        declarator: Declarator,
+    },
+    Const {
+        type_spec: TypeSpec,
+        name: String,
+        value: Literal,
+    },
+}
+
+struct Member {
+    annotations: Vec<AnnotationApplication>,
+    type_spec: TypeSpec,
+    declarators: Vec<Declarator>,
+}
+
+struct Declarator {
+    name: String,
+    array_size: Option<u64>,
+}
+
+struct AnnotationApplication {
+    name: String,
+    parameters: Vec<(Option<String>, Literal)>,
+}
+
+enum TypeSpec {
+    Base(BaseType),
+    Sequence(Box<TypeSpec>, Option<u64>),
+    Named(String),
+}
+
+#[derive(Debug)]
+enum Literal {
+    Bool(bool),
+    Integer(IntRepresentation),
+    Float(f64),
+    String(String),
+    Identifier,
+}
+
+// -- Start: Parsing
+
+fn parse_specification(input: &str) -> IResult<&str, Vec<Definition>> {
+    all_consuming(terminated(many0(parse_definition), skip))(input)
+}
+
+fn parse_definition(input: &str) -> IResult<&str, Definition> {
+    map(
+        terminated(
+            pair(
+                many0(parse_annotation),
+                alt((parse_module, parse_struct, parse_typedef, parse_const)),
+            ),
+            token(char(';')),
+        ),
+        |(annotations, kind)| Definition { annotations, kind },
+    )(input)
+}
+
+fn parse_module(input: &str) -> IResult<&str, DefinitionKind> {
+    map(
+        tuple((
+            keyword("module"),
+            identifier,
+            delimited(token(char('{')), many0(parse_definition), token(char('}'))),
+        )),
+        |(_, name, definitions)| DefinitionKind::Module { name, definitions },
+    )(input)
+}
+
+fn parse_struct(input: &str) -> IResult<&str, DefinitionKind> {
+    map(
+        tuple((
+            keyword("struct"),
+            identifier,
+            delimited(token(char('{')), many0(parse_member), token(char('}'))),
+        )),
+        |(_, name, members)| DefinitionKind::Struct { name, members },
+    )(input)
+}
+
+fn parse_typedef(input: &str) -> IResult<&str, DefinitionKind> {
+    map(
+        tuple((keyword("typedef"), parse_type_spec, parse_declarator)),
+        |(_, type_spec, declarator)| DefinitionKind::Typedef {
+            type_spec,
+            declarator,
+        },
+    )(input)
+}
+
+fn parse_const(input: &str) -> IResult<&str, DefinitionKind> {
+    map(
+        tuple((
+            keyword("const"),
+            parse_type_spec,
+            identifier,
+            preceded(token(char('=')), parse_literal),
+        )),
+        |(_, type_spec, name, value)| DefinitionKind::Const {
+            type_spec,
+            name,
+            value,
+        },
+    )(input)
+}
+
+fn parse_member(input: &str) -> IResult<&str, Member> {
+    map(
+        tuple((
+            many0(parse_annotation),
+            parse_type_spec,
+            terminated(
+                separated_list1(token(char(',')), parse_declarator),
+                token(char(';')),
+            ),
+        )),
+        |(annotations, type_spec, declarators)| Member {
+            annotations,
+            type_spec,
+            declarators,
+        },
+    )(input)
+}
+
+fn parse_declarator(input: &str) -> IResult<&str, Declarator> {
+    map(
+        pair(
+            identifier,
+            opt(delimited(
+                token(char('[')),
+                token(parse_u64),
+                token(char(']')),
+            )),
+        ),
+        |(name, array_size)| Declarator { name, array_size },
+    )(input)
+}
+
+fn parse_annotation(input: &str) -> IResult<&str, AnnotationApplication> {
+    map(
+        preceded(
+            token(char('@')),
+            pair(
+                identifier,
+                opt(delimited(
+                    token(char('(')),
+                    separated_list0(
+                        token(char(',')),
+                        pair(opt(terminated(identifier, token(char('=')))), parse_literal),
+                    ),
+                    token(char(')')),
+                )),
+            ),
+        ),
+        |(name, parameters)| AnnotationApplication {
+            name,
+            parameters: parameters.unwrap_or_default(),
+        },
+    )(input)
+}
+
+fn parse_type_spec(input: &str) -> IResult<&str, TypeSpec> {
+    alt((
+        map(
+            preceded(
+                keyword("sequence"),
+                delimited(
+                    token(char('<')),
+                    pair(
+                        parse_type_spec,
+                        opt(preceded(token(char(',')), token(parse_u64))),
+                    ),
+                    token(char('>')),
+                ),
+            ),
+            |(element_type, bound)| TypeSpec::Sequence(Box::new(element_type), bound),
+        ),
+        map(
+            preceded(keyword("wstring"), opt(parse_string_bound)),
+            |bound| TypeSpec::Base(BaseType::Wstring(bound)),
+        ),
+        map(
+            preceded(keyword("string"), opt(parse_string_bound)),
+            |bound| TypeSpec::Base(BaseType::String(bound)),
+        ),
+        map(parse_primitive_type, TypeSpec::Base),
+        map(scoped_name, TypeSpec::Named),
+    ))(input)
+}
+
+fn parse_string_bound(input: &str) -> IResult<&str, u64> {
+    delimited(token(char('<')), token(parse_u64), token(char('>')))(input)
+}
+
+fn parse_primitive_type(input: &str) -> IResult<&str, BaseType> {
+    alt((
+        alt((
+            value(BaseType::Bool, keyword("boolean")),
+            value(BaseType::Byte, keyword("octet")),
+            value(BaseType::Char, keyword("char")),
+            value(BaseType::Float32, keyword("float")),
+            value(BaseType::Float64, keyword("double")),
+            value(BaseType::Int8, keyword("int8")),
+            value(BaseType::Uint8, keyword("uint8")),
+            value(BaseType::Int16, keyword("int16")),
+            value(BaseType::Uint16, keyword("uint16")),
+            value(BaseType::Int32, keyword("int32")),
+            value(BaseType::Uint32, keyword("uint32")),
+            value(BaseType::Int64, keyword("int64")),
+            value(BaseType::Uint64, keyword("uint64")),
+        )),
+        alt((
+            value(
+                BaseType::Uint64,
+                tuple((keyword("unsigned"), keyword("long"), keyword("long"))),
+            ),
+            value(BaseType::Uint32, pair(keyword("unsigned"), keyword("long"))),
+            value(
+                BaseType::Uint16,
+                pair(keyword("unsigned"), keyword("short")),
+            ),
+            value(BaseType::Int64, pair(keyword("long"), keyword("long"))),
+            value(BaseType::Int32, keyword("long")),
+            value(BaseType::Int16, keyword("short")),
+        )),
+    ))(input)
+}
+
+fn parse_literal(input: &str) -> IResult<&str, Literal> {
+    token(alt((
+        // adjacent string literals are concatenated
+        map(
+            many1(token(preceded(opt(char('L')), parse_quoted_string))),
+            |parts| Literal::String(parts.concat()),
+        ),
+        map(parse_bool_literal, Literal::Bool),
+        map(parse_float_literal, Literal::Float),
+        map(
+            alt((parse_idl_octal_literal, parse_int_literal)),
+            Literal::Integer,
+        ),
+        map(scoped_name, |_| Literal::Identifier),
+    )))(input)
+}
+
+fn parse_bool_literal(input: &str) -> IResult<&str, bool> {
+    terminated(
+        alt((
+            value(true, alt((tag("TRUE"), tag("True"), tag("true")))),
+            value(false, alt((tag("FALSE"), tag("False"), tag("false")))),
+        )),
+        not(satisfy(is_identifier_char)),
+    )(input)
+}
+
+fn parse_float_literal(input: &str) -> IResult<&str, f64> {
+    map_res(
+        recognize(pair(
+            opt(one_of("+-")),
+            alt((
+                recognize(tuple((digit1, char('.'), digit0, opt(parse_exponent)))),
+                recognize(tuple((char('.'), digit1, opt(parse_exponent)))),
+                recognize(pair(digit1, parse_exponent)),
+            )),
+        )),
+        str::parse::<f64>,
+    )(input)
+}
+
+fn parse_exponent(input: &str) -> IResult<&str, &str> {
+    recognize(tuple((one_of("eE"), opt(one_of("+-")), digit1)))(input)
+}
+
+fn parse_idl_octal_literal(input: &str) -> IResult<&str, IntRepresentation> {
+    map_res(preceded(char('0'), oct_digit1), |digits: &str| {
+        u64::from_str_radix(digits, 8).map(IntRepresentation::Octal)
+    })(input)
+}
+
+fn parse_u64(input: &str) -> IResult<&str, u64> {
+    map_res(digit1, str::parse::<u64>)(input)
+}
+
+// a leading underscore escapes identifiers which would otherwise be keywords
+fn identifier(input: &str) -> IResult<&str, String> {
+    token(map(raw_identifier, |identifier| {
+        identifier
+            .strip_prefix('_')
+            .unwrap_or(identifier)
+            .to_string()
+    }))(input)
+}
+
+fn raw_identifier(input: &str) -> IResult<&str, &str> {
+    recognize(pair(
+        satisfy(|c| c.is_ascii_alphabetic() || c == '_'),
+        take_while(is_identifier_char),
+    ))(input)
+}
+
+fn scoped_name(input: &str) -> IResult<&str, String> {
+    token(map(
+        preceded(
+            opt(tag("::")),
+            recognize(separated_list1(tag("::"), raw_identifier)),
+        ),
+        String::from,
+    ))(input)
+}
+
+fn is_identifier_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+fn keyword<'a>(word: &'static str) -> impl FnMut(&'a str) -> IResult<&'a str, &'a str> {
+    token(terminated(tag(word), not(satisfy(is_identifier_char))))
+}
+
+fn token<'a, O, P>(parser: P) -> impl FnMut(&'a str) -> IResult<&'a str, O>
+where
+    P: FnMut(&'a str) -> IResult<&'a str, O>,
+{
+    preceded(skip, parser)
+}
+
+// skips whitespace, comments and preprocessor directives such as #include
+fn skip(input: &str) -> IResult<&str, ()> {
+    value(
+        (),
+        many0(alt((
+            multispace1,
+            recognize(pair(tag("//"), opt(is_not("\r\n")))),
+            recognize(tuple((tag("/*"), take_until("*/"), tag("*/")))),
+            recognize(pair(tag("#"), opt(is_not("\r\n")))),
+        ))),
+    )(input)
+}
+
+// -- End: Parsing
+
+// -- Start: Conversion
+
+#[derive(Default)]
+struct Declarations<'a> {
+    structs: Vec<(Vec<&'a str>, &'a str, &'a [Member])>,
+    typedefs: HashMap<String, (&'a TypeSpec, Option<u64>)>,
+    constants: HashMap<String, Vec<&'a Definition>>,
+}
+
+fn convert_definitions(definitions: &[Definition]) -> Result<Vec<StructuredType>> {
+    let mut declarations = Declarations::default();
+    collect_declarations(definitions, &mut Vec::new(), &mut declarations);
+
+    let mut structured_types = Vec::new();
+    for (module_path, name, members) in &declarations.structs {
+        let mut fields = Vec::new();
+        let module_name = module_path.join("::");
+        let constants_module_name =
+            qualified_name(module_path, &format!("{name}{CONSTANTS_MODULE_SUFFIX}"));
+        let structs_in_module = declarations
+            .structs
+            .iter()
+            .filter(|(other_module_path, _, _)| other_module_path == module_path)
+            .count();
+        if let Some(constants) = declarations.constants.get(&module_name) {
+            if structs_in_module != 1 {
+                return Err(format!(
+                    "Constants of module \"{module_name}\" cannot be assigned to a single struct, \
+                    declare them in a module named \"<Struct>{CONSTANTS_MODULE_SUFFIX}\" instead"
+                )
+                .into());
+            }
+            for constant in constants {
+                fields.push(convert_constant(constant, module_path, &declarations)?);
+            }
+        }
+        if let Some(constants) = declarations.constants.get(&constants_module_name) {
+            for constant in constants {
+                fields.push(convert_constant(constant, module_path, &declarations)?);
+            }
+        }
+        for member in members.iter() {
+            fields.extend(convert_member(member, module_path, &declarations)?);
+        }
+        structured_types.push(StructuredType::new(name.to_string(), fields));
+    }
+    if structured_types.is_empty() {
+        return Err("No struct found in IDL file".into());
+    }
+    Ok(structured_types)
+}
+
+fn collect_declarations<'a>(
+    definitions: &'a [Definition],
+    module_path: &mut Vec<&'a str>,
+    declarations: &mut Declarations<'a>,
+) {
+    for definition in definitions {
+        match &definition.kind {
+            DefinitionKind::Module { name, definitions } => {
+                module_path.push(name);
+                collect_declarations(definitions, module_path, declarations);
+                module_path.pop();
+            }
+            DefinitionKind::Struct { name, members } => {
+                declarations
+                    .structs
+                    .push((module_path.clone(), name, members));
+            }
+            DefinitionKind::Typedef {
+                type_spec,
+                declarator,
+            } => {
+                declarations.typedefs.insert(
+                    qualified_name(module_path, &declarator.name),
+                    (type_spec, declarator.array_size),
+                );
+            }
+            DefinitionKind::Const { .. } => {
+                declarations
+                    .constants
+                    .entry(module_path.join("::"))
+                    .or_default()
+                    .push(definition);
+            }
+        }
+    }
+}
+
+fn convert_constant(
+    definition: &Definition,
+    module_path: &[&str],
+    declarations: &Declarations,
+) -> Result<Field> {
+    let DefinitionKind::Const {
+        type_spec,
+        name,
+        value,
+    } = &definition.kind
+    else {
+        return Err("Expected a constant declaration".into());
+    };
+    let (base_type, array_size) = resolve_type(type_spec, module_path, declarations)?;
+    let initial_value = convert_literal(value, &base_type, array_size.as_ref())
+        .map_err(|err| format!("Invalid value of constant \"{name}\": {err}"))?;
+    let annotations =
+        convert_annotations(&definition.annotations, &base_type, array_size.as_ref(), name)?;
+    Ok(Field::new(
+        name.clone(),
+        base_type,
+        array_size,
+        FieldType::Constant,
+        Some(initial_value),
+        get_comment(&definition.annotations, name),
+        annotations,
+    ))
+}
+
+fn convert_member(
+    member: &Member,
+    module_path: &[&str],
+    declarations: &Declarations,
+) -> Result<Vec<Field>> {
+    let mut fields = Vec::new();
+    for declarator in &member.declarators {
+        if declarator.name == PLACEHOLDER_MEMBER_NAME {
+            continue;
+        }
+        let name = &declarator.name;
+        let (base_type, array_size) = with_array_size(
+            resolve_type(&member.type_spec, module_path, declarations)?,
+            declarator.array_size,
+        )?;
+        let annotations =
+            convert_annotations(&member.annotations, &base_type, array_size.as_ref(), name)?;
+        let initial_value = annotations.iter().find_map(|annotation| match annotation {
+            Annotation::Default(initial_value) => Some(initial_value.clone()),
+            _ => None,
+        });
+        fields.push(Field::new(
+            name.clone(),
+            base_type,
+            array_size,
+            FieldType::Variable,
+            initial_value,
+            get_comment(&member.annotations, name),
+            annotations,
+        ));
+    }
+    Ok(fields)
+}
+
+fn resolve_type(
+    type_spec: &TypeSpec,
+    module_path: &[&str],
+    declarations: &Declarations,
+) -> Result<(BaseType, Option<ArraySize>)> {
+    match type_spec {
+        TypeSpec::Base(base_type) => Ok((base_type.clone(), None)),
+        TypeSpec::Sequence(element_type, bound) => {
+            let (base_type, array_size) = resolve_type(element_type, module_path, declarations)?;
+            if array_size.is_some() {
+                return Err("Nested sequences and arrays are not supported".into());
+            }
+            Ok((
+                base_type,
+                Some(bound.map_or(ArraySize::Dynamic, ArraySize::BoundDynamic)),
+            ))
+        }
+        TypeSpec::Named(scoped_name) => {
+            match find_typedef(scoped_name, module_path, declarations) {
+                Some((type_spec, array_size)) => with_array_size(
+                    resolve_type(type_spec, module_path, declarations)?,
+                    array_size,
+                ),
+                None => Ok((
+                    BaseType::Custom(convert_to_reference(scoped_name, module_path)?),
+                    None,
+                )),
+            }
+        }
+    }
+}
+
+fn with_array_size(
+    resolved_type: (BaseType, Option<ArraySize>),
+    array_size: Option<u64>,
+) -> Result<(BaseType, Option<ArraySize>)> {
+    match (resolved_type, array_size) {
+        (resolved_type, None) => Ok(resolved_type),
+        ((base_type, None), Some(capacity)) => Ok((base_type, Some(ArraySize::Capacity(capacity)))),
+        ((_, Some(_)), Some(_)) => Err("Nested sequences and arrays are not supported".into()),
+    }
+}
+
+fn find_typedef<'a>(
+    scoped_name: &str,
+    module_path: &[&str],
+    declarations: &Declarations<'a>,
+) -> Option<(&'a TypeSpec, Option<u64>)> {
+    (0..=module_path.len())
+        .rev()
+        .find_map(|depth| {
+            declarations
+                .typedefs
+                .get(&qualified_name(&module_path[..depth], scoped_name))
+        })
+        .copied()
+}
+
+fn convert_to_reference(scoped_name: &str, module_path: &[&str]) -> Result<Reference> {
+    let own_package = module_path.first().copied();
+    let parts: Vec<&str> = scoped_name.split("::").collect();
+    match parts.as_slice() {
+        [file] => Ok(Reference::Relative {
+            file: file.to_string(),
+        }),
+        [package, file] | [package, "msg", file] if Some(*package) == own_package => {
+            Ok(Reference::Relative {
+                file: file.to_string(),
+            })
+        }
+        [package, file] | [package, "msg", file] => Ok(Reference::Absolute {
+            package: package.to_string(),
+            file: file.to_string(),
+        }),
+        _ => Err(format!("Type \"{scoped_name}\" cannot be referenced").into()),
+    }
+}
+
+fn qualified_name(module_path: &[&str], name: &str) -> String {
+    module_path
+        .iter()
+        .copied()
+        .chain(std::iter::once(name))
+        .collect::<Vec<&str>>()
+        .join("::")
+}
+
+fn get_parameter<'a>(annotation: &'a AnnotationApplication, name: &str) -> Option<&'a Literal> {
+    get_named_parameter(annotation, name).or_else(|| {
+        // a single unnamed parameter is the value of the annotation, e.g. @default(0)
+        annotation
+            .parameters
+            .first()
+            .filter(|(parameter_name, _)| parameter_name.is_none())
+            .map(|(_, literal)| literal)
+    })
+}
+
+fn get_named_parameter<'a>(
+    annotation: &'a AnnotationApplication,
+    name: &str,
+) -> Option<&'a Literal> {
+    annotation
+        .parameters
+        .iter()
+        .find(|(parameter_name, _)| parameter_name.as_deref() == Some(name))
+        .map(|(_, literal)| literal)
+}
+
+fn get_comment(annotations: &[AnnotationApplication], name: &str) -> Option<String> {
+    let mut comments = Vec::new();
+    for annotation in annotations
+        .iter()
+        .filter(|annotation| annotation.name == "verbatim")
+    {
+        if let Some(Literal::String(language)) = get_named_parameter(annotation, "language") {
+            if language != "comment" {
+                debug!("Ignoring @verbatim of \"{name}\" for language {language:?}");
+                continue;
+            }
+        }
+        if let Some(Literal::String(text)) = get_parameter(annotation, "text") {
+            comments.push(text.clone());
+        }
+    }
+    (!comments.is_empty()).then(|| comments.join(" "))
+}
+
+fn convert_annotations(
+    annotations: &[AnnotationApplication],
+    base_type: &BaseType,
+    array_size: Option<&ArraySize>,
+    name: &str,
+) -> Result<Vec<Annotation>> {
+    let mut converted = Vec::new();
+    for annotation in annotations {
+        match annotation.name.as_str() {
+            "key" => converted.push(Annotation::Key),
+            "unit" => match get_parameter(annotation, "value") {
+                Some(Literal::String(unit)) => converted.push(Annotation::Unit(unit.clone())),
+                _ => return Err(format!("Expected a string as @unit of \"{name}\"").into()),
+            },
+            "range" => {
+                // the bounds are either named or given in the order min, max
+                let convert_bound = |bound_name: &str, position: usize| {
+                    annotation
+                        .parameters
+                        .iter()
+                        .find(|(parameter_name, _)| parameter_name.as_deref() == Some(bound_name))
+                        .or_else(|| {
+                            annotation
+                                .parameters
+                                .get(position)
+                                .filter(|(parameter_name, _)| parameter_name.is_none())
+                        })
+                        .map(|(_, literal)| convert_literal(literal, base_type, None))
+                        .transpose()
+                        .map_err(|err| format!("Invalid @range of \"{name}\": {err}"))
+                };
+                let min = convert_bound("min", 0)?;
+                let max = convert_bound("max", 1)?;
+                if min.is_none() && max.is_none() {
+                    return Err(format!("Expected a min or max in @range of \"{name}\"").into());
+                }
+                converted.push(Annotation::Range { min, max });
+            }
+            "default" => match get_parameter(annotation, "value") {
+                Some(literal) => converted.push(Annotation::Default(
+                    convert_literal(literal, base_type, array_size)
+                        .map_err(|err| format!("Invalid default value of \"{name}\": {err}"))?,
+                )),
+                None => return Err(format!("Expected a value in @default of \"{name}\"").into()),
+            },
+            "verbatim" => {
+                let language = match get_named_parameter(annotation, "language") {
+                    Some(Literal::String(language)) => Some(language.clone()),
+                    _ => None,
+                };
+                match get_parameter(annotation, "text") {
+                    Some(Literal::String(text)) => converted.push(Annotation::Verbatim {
+                        language,
+                        text: text.clone(),
+                    }),
+                    _ => {
+                        return Err(
+                            format!("Expected a string as text of @verbatim of \"{name}\"").into()
+                        )
+                    }
+                }
+            }
+            other => debug!("Ignoring annotation @{other} of \"{name}\""),
+        }
+    }
+    Ok(converted)
+}
+
+fn convert_literal(
+    literal: &Literal,
+    base_type: &BaseType,
+    array_size: Option<&ArraySize>,
+) -> Result<InitialValue> {
+    if array_size.is_some() {
+        // rosidl writes array defaults as a string containing a tuple, e.g. "(1, 2, 3)"
+        let Literal::String(elements) = literal else {
+            return Err(format!("Expected a sequence literal, found {literal:?}").into());
+        };
+        let elements = parse_sequence_literal(elements)
+            .map_err(|err| err.to_owned())
+            .finish()?
+            .1;
+        return elements
+            .iter()
+            .map(|element| convert_literal(element, base_type, None))
+            .collect::<Result<Vec<InitialValue>>>()
+            .map(InitialValue::Array);
+    }
+    let initial_value = match (base_type, literal) {
+        (BaseType::Bool, Literal::Bool(bool)) => {
+            InitialValue::Bool(BoolRepresentation::String(*bool))
+        }
+        (BaseType::Bool, Literal::Integer(IntRepresentation::UnsignedDecimal(u64 @ (0 | 1)))) => {
+            InitialValue::Bool(BoolRepresentation::Binary(*u64 == 1))
+        }
+        (BaseType::Byte, Literal::Integer(int)) => InitialValue::Byte(int.clone()),
+        (BaseType::Uint8, Literal::Integer(int)) => InitialValue::Uint8(int.clone()),
+        (BaseType::Uint16, Literal::Integer(int)) => InitialValue::Uint16(int.clone()),
+        (BaseType::Uint32, Literal::Integer(int)) => InitialValue::Uint32(int.clone()),
+        (BaseType::Uint64, Literal::Integer(int)) => InitialValue::Uint64(int.clone()),
+        (BaseType::Int8, Literal::Integer(int)) => InitialValue::Int8(int.clone()),
+        (BaseType::Int16, Literal::Integer(int)) => InitialValue::Int16(int.clone()),
+        (BaseType::Int32, Literal::Integer(int)) => InitialValue::Int32(int.clone()),
+        (BaseType::Int64, Literal::Integer(int)) => InitialValue::Int64(int.clone()),
+        (BaseType::Float32, Literal::Float(f64)) => InitialValue::Float32(*f64 as f32),
+        (BaseType::Float32, Literal::Integer(int)) => {
+            InitialValue::Float32(int_representation_as_f64(int) as f32)
+        }
+        (BaseType::Float64, Literal::Float(f64)) => InitialValue::Float64(*f64),
+        (BaseType::Float64, Literal::Integer(int)) => {
+            InitialValue::Float64(int_representation_as_f64(int))
+        }
+        (BaseType::Char, Literal::Integer(int)) => InitialValue::Char(int.clone()),
+        (BaseType::Char, Literal::String(string)) if string.chars().count() == 1 => {
+            let char = string.chars().next().unwrap_or_default();
+            if u32::from(char) > 0xFF {
+                return Err(format!("A char holds a single byte, which {char:?} exceeds").into());
+            }
+            InitialValue::Char(IntRepresentation::UnsignedDecimal(u32::from(char).into()))
+        }
+        (BaseType::String(_), Literal::String(string)) => InitialValue::String(string.clone()),
+        (BaseType::Wstring(_), Literal::String(string)) => InitialValue::Wstring(string.clone()),
+        (base_type, literal) => {
+            return Err(format!("{literal:?} is not a valid value of type {base_type:?}").into())
+        }
+    };
+    Ok(initial_value)
+}
+
+fn parse_sequence_literal(input: &str) -> IResult<&str, Vec<Literal>> {
+    all_consuming(delimited(
+        token(one_of("([")),
+        terminated(
+            separated_list0(token(char(',')), parse_literal),
+            opt(token(char(','))),
+        ),
+        terminated(token(one_of(")]")), skip),
+    ))(input)
+}
+
+fn int_representation_as_f64(int_representation: &IntRepresentation) -> f64 {
+    match int_representation {
+        IntRepresentation::SignedDecimal(i64) => *i64 as f64,
+        IntRepresentation::UnsignedDecimal(u64)
+        | IntRepresentation::Binary(u64)
+        | IntRepresentation::Octal(u64)
+        | IntRepresentation::Hexadecimal(u64) => *u64 as f64,
+    }
+}
+
+// -- End: Conversion
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::business::dtp_converter::converter::{self as dtp_converter, ArrayPadding};
+    use crate::business::dtp_converter::idl_writer;
+    use crate::business::msg_converter::converter::{self as msg_converter, TypeNaming};
+    use crate::business::roundtrip;
+    use crate::core::dtp;
+
+    fn field(name: &str, base_type: BaseType, array_size: Option<ArraySize>) -> Field {
+        Field::new(
+            name.to_string(),
+            base_type,
+            array_size,
+            FieldType::Variable,
+            None,
+            None,
+            Vec::new(),
+        )
+    }
+
+    #[test]
+    fn reads_members_typedefs_and_constants() {
+        let structured_types = parse(
+            r#"
+            #include "other_msgs/msg/Other.idl"
+            module pkg {
+              module msg {
+                typedef double double__3[3];
+                module Sample_Constants {
+                  /* the answer */
+                  const int32 ANSWER = 42;
+                };
+                struct Sample {
+                  @verbatim (language="comment", text="a flag")
+                  boolean flag;
+                  double__3 values;
+                  sequence<string<8>, 4> names;
+                  char letter, initial;
+                  other_msgs::msg::Other other;
+                };
+              };
+            };
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            structured_types,
+            vec![StructuredType::new(
+                "Sample".to_string(),
+                vec![
+                    Field::new(
+                        "ANSWER".to_string(),
+                        BaseType::Int32,
+                        None,
+                        FieldType::Constant,
+                        Some(InitialValue::Int32(IntRepresentation::UnsignedDecimal(42))),
+                        None,
+                        Vec::new(),
+                    ),
+                    Field::new(
+                        "flag".to_string(),
+                        BaseType::Bool,
+                        None,
+                        FieldType::Variable,
+                        None,
+                        Some("a flag".to_string()),
+                        vec![Annotation::Verbatim {
+                            language: Some("comment".to_string()),
+                            text: "a flag".to_string(),
+                        }],
+                    ),
+                    field("values", BaseType::Float64, Some(ArraySize::Capacity(3))),
+                    field(
+                        "names",
+                        BaseType::String(Some(8)),
+                        Some(ArraySize::BoundDynamic(4)),
+                    ),
+                    field("letter", BaseType::Char, None),
+                    field("initial", BaseType::Char, None),
+                    field(
+                        "other",
+                        BaseType::Custom(Reference::Absolute {
+                            package: "other_msgs".to_string(),
+                            file: "Other".to_string(),
+                        }),
+                        None,
+                    ),
+                ],
+            )]
+        );
+    }
+
+    #[test]
+    fn reads_annotations_with_named_and_unnamed_parameters() {
+        let structured_types = parse(
+            r#"
+            module pkg { module msg { struct Sample {
+              @default (value=1.5) @unit ("m") @key
+              double distance;
+              @default ("(1, 2)")
+              int8 pair[2];
+            }; }; };
+            "#,
+        )
+        .unwrap();
+        let fields = structured_types[0].fields();
+        assert_eq!(fields[0].initial_value(), Some(&InitialValue::Float64(1.5)));
+        assert_eq!(
+            fields[0].annotations(),
+            &vec![
+                Annotation::Default(InitialValue::Float64(1.5)),
+                Annotation::Unit("m".to_string()),
+                Annotation::Key
+            ]
+        );
+        let pair = InitialValue::Array(vec![
+            InitialValue::Int8(IntRepresentation::UnsignedDecimal(1)),
+            InitialValue::Int8(IntRepresentation::UnsignedDecimal(2)),
+        ]);
+        assert_eq!(fields[1].initial_value(), Some(&pair));
+        assert_eq!(fields[1].annotations(), &vec![Annotation::Default(pair)]);
+    }
+
+    #[test]
+    fn reads_named_and_positional_range_bounds() {
+        let range = |annotation: &str| {
+            parse(&format!(
+                "module pkg {{ module msg {{ struct Sample {{ {annotation} int8 value; }}; }}; }};"
+            ))
+            .map(|structured_types| structured_types[0].fields()[0].annotations().clone())
+        };
+        let bound = |u64| Some(InitialValue::Int8(IntRepresentation::UnsignedDecimal(u64)));
+        assert_eq!(
+            range("@range (min=1, max=5)").unwrap(),
+            vec![Annotation::Range { min: bound(1), max: bound(5) }]
+        );
+        assert_eq!(
+            range("@range (1, 5)").unwrap(),
+            vec![Annotation::Range { min: bound(1), max: bound(5) }]
+        );
+        assert_eq!(
+            range("@range (max=5)").unwrap(),
+            vec![Annotation::Range { min: None, max: bound(5) }]
+        );
+        assert!(range("@range").is_err());
+        assert!(range("@range (step=2)").is_err());
+    }
+
+    #[test]
+    fn named_parameters_are_not_taken_for_other_parameters() {
+        let read = |annotation: &str| {
+            parse(&format!(
+                "module pkg {{ module msg {{ struct Sample {{ {annotation} int8 value; }}; }}; }};"
+            ))
+        };
+        assert!(read("@default (fallback=7)").is_err());
+        assert!(read("@verbatim (language=\"comment\")").is_err());
+        assert!(read("@unit (symbol=\"m\")").is_err());
+        assert!(read("@default (7)").is_ok());
+    }
+
+    #[test]
+    fn rejects_unsupported_specifications() {
+        assert!(parse("module pkg { module msg { }; };").is_err());
+        assert!(parse(
+            "module pkg { module msg { struct Sample { sequence<sequence<int8>> nested; }; }; };"
+        )
+        .is_err());
+        assert!(parse(
+            "module pkg { module msg { struct Sample { @default (value=\"text\") int8 value; }; }; };"
+        )
+        .is_err());
+        assert!(parse("module pkg { module msg { struct Sample { int8 value } }; };").is_err());
+    }
+
+    #[test]
+    fn validates_constants() {
+        let constant = |declaration: &str| {
+            parse(&format!(
+                "module pkg {{ module msg {{ module Sample_Constants {{ {declaration} }}; \
+                struct Sample {{ int8 value; }}; }}; }};"
+            ))
+        };
+        assert!(constant("const int8 ANSWER = 42;").is_ok());
+        assert!(constant("const int8 answer = 42;").is_err());
+    }
+
+    #[test]
+    fn reads_what_the_idl_writer_wrote() {
+        let structured_type = StructuredType::new(
+            "Sample".to_string(),
+            vec![
+                Field::new(
+                    "letter".to_string(),
+                    BaseType::Char,
+                    None,
+                    FieldType::Variable,
+                    Some(InitialValue::Char(IntRepresentation::UnsignedDecimal(65))),
+                    Some("first letter".to_string()),
+                    vec![
+                        Annotation::Verbatim {
+                            language: Some("comment".to_string()),
+                            text: "first letter".to_string(),
+                        },
+                        Annotation::Verbatim {
+                            language: Some("c++".to_string()),
+                            text: "// generated".to_string(),
+                        },
+                        Annotation::Default(InitialValue::Char(
+                            IntRepresentation::UnsignedDecimal(65),
+                        )),
+                    ],
+                ),
+                field("count", BaseType::Uint8, Some(ArraySize::Dynamic)),
+            ],
+        );
+        assert_eq!(write_and_read(&structured_type), vec![structured_type]);
+    }
+
+    #[test]
+    fn reads_keywords_the_idl_writer_escaped() {
+        let structured_type = StructuredType::new(
+            "Map".to_string(),
+            vec![
+                field("module", BaseType::Int8, None),
+                field("port", BaseType::String(None), Some(ArraySize::Capacity(2))),
+            ],
+        );
+        assert_eq!(write_and_read(&structured_type), vec![structured_type]);
+    }
+
+    #[test]
+    fn reads_the_floats_the_idl_writer_wrote() {
+        let float64 = |f64| {
+            Field::new(
+                "value".to_string(),
+                BaseType::Float64,
+                None,
+                FieldType::Variable,
+                Some(InitialValue::Float64(f64)),
+                None,
+                vec![Annotation::Default(InitialValue::Float64(f64))],
+            )
+        };
+        for f64 in [1e300, -2.5e-10, 1.0, 0.1, -0.0, f64::MAX, f64::MIN_POSITIVE] {
+            let structured_type = StructuredType::new("Floats".to_string(), vec![float64(f64)]);
+            assert_eq!(write_and_read(&structured_type), vec![structured_type]);
+        }
+        let float32 = 3.4e38f32;
+        let structured_type = StructuredType::new(
+            "Floats".to_string(),
+            vec![Field::new(
+                "value".to_string(),
+                BaseType::Float32,
+                Some(ArraySize::Capacity(2)),
+                FieldType::Variable,
+                Some(InitialValue::Array(vec![
+                    InitialValue::Float32(float32),
+                    InitialValue::Float32(1e-30),
+                ])),
+                None,
+                vec![Annotation::Default(InitialValue::Array(vec![
+                    InitialValue::Float32(float32),
+                    InitialValue::Float32(1e-30),
+                ]))],
+            )],
+        );
+        assert_eq!(write_and_read(&structured_type), vec![structured_type]);
+    }
+
+    #[test]
+    fn the_idl_writer_rejects_non_finite_floats() {
+        let directory = std::env::temp_dir()
+            .join(format!("rossydiac-{}", std::process::id()))
+            .join("idl_non_finite");
+        std::fs::create_dir_all(&directory).unwrap();
+        let directory = format!("{}/", directory.display());
+        for f64 in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            let structured_type = StructuredType::new(
+                "NonFinite".to_string(),
+                vec![Field::new(
+                    "value".to_string(),
+                    BaseType::Float64,
+                    None,
+                    FieldType::Variable,
+                    Some(InitialValue::Float64(f64)),
+                    None,
+                    Vec::new(),
+                )],
+            );
+            assert!(idl_writer::write(&structured_type, "pkg", &directory).is_err());
+        }
+    }
+
+    #[test]
+    fn annotations_are_kept_as_attributes() {
+        let structured_types = parse(
+            r#"
+            module pkg {
+              module msg {
+                struct Annotated {
+                  @range (1)
+                  @default (value=3)
+                  @verbatim (language="c++", text="// generated")
+                  int8 value;
+                };
+              };
+            };
+            "#,
+        )
+        .unwrap();
+        let version_info = dtp::VersionInfo::new(None, None, None, None, None);
+        let data_type = msg_converter::convert(
+            "pkg",
+            &structured_types[0],
+            &version_info,
+            TypeNaming::Packaged,
+        )
+        .unwrap();
+        let attribute_names: Vec<&str> = data_type.structured_type().var_declarations()[0]
+            .attributes()
+            .iter()
+            .map(dtp::Attribute::name)
+            .collect();
+        assert_eq!(
+            attribute_names,
+            vec![
+                dtp::ANNOTATION_NAME_ROS2_RANGE_MIN,
+                dtp::ANNOTATION_NAME_ROS2_DEFAULT,
+                dtp::ANNOTATION_NAME_ROS2_VERBATIM
+            ]
+        );
+        let round_tripped =
+            dtp_converter::convert("pkg", &data_type, ArrayPadding::Reject).unwrap();
+        assert!(roundtrip::diff_msg(&structured_types[0], &round_tripped).is_empty());
+    }
+
+    #[test]
+    fn chars_beyond_a_byte_are_rejected() {
+        let char_member = |default: &str| {
+            parse(&format!(
+                "module pkg {{ module msg {{ struct Letter {{
+                  @default (value={default}) char letter;
+                }}; }}; }};"
+            ))
+        };
+        let convert = |structured_types: Vec<StructuredType>| {
+            let version_info = dtp::VersionInfo::new(None, None, None, None, None);
+            msg_converter::convert(
+                "pkg",
+                &structured_types[0],
+                &version_info,
+                TypeNaming::Packaged,
+            )
+        };
+        assert!(convert(char_member("'\u{FF}'").unwrap()).is_ok());
+        assert!(convert(char_member("255").unwrap()).is_ok());
+        assert!(char_member("'\u{12C}'")
+            .unwrap_err()
+            .to_string()
+            .contains("single byte"));
+        assert!(convert(char_member("256").unwrap()).is_err());
+        assert!(convert(char_member("-1").unwrap()).is_err());
+    }
+
+    fn write_and_read(structured_type: &StructuredType) -> Vec<StructuredType> {
+        let directory = std::env::temp_dir()
+            .join(format!("rossydiac-{}", std::process::id()))
+            .join(format!("idl_write_read_{}", structured_type.name()));
+        std::fs::create_dir_all(&directory).unwrap();
+        let directory = format!("{}/", directory.display());
+        idl_writer::write(structured_type, "pkg", &directory).unwrap();
+        read(&format!("{directory}{}.idl", structured_type.name())).unwrap()
+    }
+}
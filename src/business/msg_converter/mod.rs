@@ -1,3 +1,11 @@
 pub mod msg_reader;
 pub mod dtp_writer;
 pub mod converter;
+pub mod idl_reader;
+pub mod fbt_generator;
+pub mod fbt_writer;
+pub mod adp_generator;
+pub mod adp_writer;
+pub mod forte_writer;
+pub mod typelib_writer;
+pub mod version_info_generator;
@@ -5,15 +5,16 @@ use log::info;
 use nom::branch::alt;
 use nom::bytes::complete::{is_a, tag, take_till1, take_while1};
 use nom::character::complete::{
-    digit1, hex_digit1, line_ending, multispace0, multispace1, oct_digit1,
+    digit1, hex_digit1, line_ending, multispace0, multispace1, oct_digit1, space0,
 };
-use nom::combinator::{eof, fail, map, map_res, opt, verify};
+use nom::combinator::{eof, fail, map, map_res, opt, peek, verify};
 use nom::multi::{many0, separated_list0};
 use nom::number::complete::{double, float};
 use nom::sequence::{delimited, preceded, terminated, tuple};
 use nom::{Finish, IResult};
 
 use crate::business::error::Result;
+use crate::business::validation;
 use crate::core::msg::*;
 
 pub fn read(path_to_source_file: &str) -> Result<StructuredType> {
@@ -21,12 +22,45 @@ pub fn read(path_to_source_file: &str) -> Result<StructuredType> {
     let source_file_path = Path::new(path_to_source_file);
     let file_name = parse_file_name(source_file_path)?;
     let file_content = std::fs::read_to_string(source_file_path)?;
-    let parsed_fields = parse_file(&file_content).finish()?.1;
-    let structured_type = StructuredType::new(file_name, parsed_fields);
+    let structured_type = parse(file_name, &file_content)?;
     info!("Finished reading file {:?}", path_to_source_file);
     Ok(structured_type)
 }
 
+pub fn parse(name: String, content: &str) -> Result<StructuredType> {
+    let parsed_fields = parse_file(content).finish()?.1;
+    validation::validate_fields(&parsed_fields)?;
+    Ok(StructuredType::new(name, parsed_fields))
+}
+
+pub fn read_service(path_to_source_file: &str) -> Result<Service> {
+    info!("Start reading file {:?}", path_to_source_file);
+    let source_file_path = Path::new(path_to_source_file);
+    let service_name = parse_file_name(source_file_path)?;
+    let file_content = std::fs::read_to_string(source_file_path)?;
+    let service = parse_service(service_name, &file_content)?;
+    info!("Finished reading file {:?}", path_to_source_file);
+    Ok(service)
+}
+
+pub fn parse_service(name: String, content: &str) -> Result<Service> {
+    let (request_content, response_content) = split_service(content)?;
+    let request = parse(format!("{name}{SERVICE_REQUEST_SUFFIX}"), request_content)?;
+    let response = parse(format!("{name}{SERVICE_RESPONSE_SUFFIX}"), response_content)?;
+    Ok(Service::new(name, request, response))
+}
+
+fn split_service(file_content: &str) -> Result<(&str, &str)> {
+    let mut offset = 0;
+    for line in file_content.split_inclusive('\n') {
+        if line.trim() == SERVICE_SEPARATOR {
+            return Ok((&file_content[..offset], &file_content[offset + line.len()..]));
+        }
+        offset += line.len();
+    }
+    Err(format!("Service file is missing the \"{SERVICE_SEPARATOR}\" separator line").into())
+}
+
 fn parse_file_name(path_to_file: &Path) -> Result<String> {
     let file_name = path_to_file
         .file_stem()
@@ -49,7 +83,7 @@ fn parse_field(input: &str) -> IResult<&str, Field> {
     ))(input)?;
 
     let (input, (initial_value, comment)) = tuple((
-        opt(parse_initial_value(&base_type, &optional_array_size)),
+        opt(preceded(space0, parse_initial_value(&base_type, &optional_array_size))),
         opt(preceded(multispace0, parse_line_comment)),
     ))(input)?;
 
@@ -62,6 +96,7 @@ fn parse_field(input: &str) -> IResult<&str, Field> {
             field_type,
             initial_value,
             comment,
+            Vec::new(),
         ),
     ))
 }
@@ -71,6 +106,28 @@ fn eol_or_eof(input: &str) -> IResult<&str, &str> {
 }
 
 fn parse_base_type(input: &str) -> IResult<&str, BaseType> {
+    alt((
+        // custom types may start like a primitive type, e.g. "char_msgs/Char"
+        terminated(parse_primitive_type, peek(alt((multispace1, tag("["))))),
+        map_res(take_till1(|c| c == ' ' || c == '['), |custom_type: &str| {
+            let parts: Vec<&str> = custom_type.split('/').collect();
+            if parts.len() == 2 {
+                Ok(BaseType::Custom(Reference::Absolute {
+                    package: parts[0].to_string(),
+                    file: parts[1].to_string(),
+                }))
+            } else if parts.len() == 1 {
+                Ok(BaseType::Custom(Reference::Relative {
+                    file: custom_type.to_string(),
+                }))
+            } else {
+                Err("Invalid custom type given")
+            }
+        }),
+    ))(input)
+}
+
+fn parse_primitive_type(input: &str) -> IResult<&str, BaseType> {
     alt((
         map(tag("bool"), |_| BaseType::Bool),
         map(tag("byte"), |_| BaseType::Byte),
@@ -85,47 +142,35 @@ fn parse_base_type(input: &str) -> IResult<&str, BaseType> {
         map(tag("int64"), |_| BaseType::Int64),
         map(tag("uint64"), |_| BaseType::Uint64),
         map(tag("char"), |_| BaseType::Char),
-        map(
+        map_res(
             tuple((tag("string"), opt(preceded(tag("<="), digit1)))),
             |(_, optional_bound): (&str, Option<&str>)| {
-                BaseType::String(optional_bound.map(|digits| digits.parse().unwrap()))
+                optional_bound
+                    .map(str::parse)
+                    .transpose()
+                    .map(BaseType::String)
             },
         ),
-        map(
+        map_res(
             tuple((tag("wstring"), opt(preceded(tag("<="), digit1)))),
             |(_, optional_bound): (&str, Option<&str>)| {
-                BaseType::Wstring(optional_bound.map(|digits| digits.parse().unwrap()))
+                optional_bound
+                    .map(str::parse)
+                    .transpose()
+                    .map(BaseType::Wstring)
             },
         ),
-        map_res(take_till1(|c| c == ' ' || c == '['), |custom_type: &str| {
-            let parts: Vec<&str> = custom_type.split('/').collect();
-            if parts.len() == 2 {
-                Ok(BaseType::Custom(Reference::Absolute {
-                    package: parts[0].to_string(),
-                    file: parts[1].to_string(),
-                }))
-            } else if parts.len() == 1 {
-                Ok(BaseType::Custom(Reference::Relative {
-                    file: custom_type.to_string(),
-                }))
-            } else {
-                Err("Invalid custom type given")
-            }
-        }),
     ))(input)
 }
 
 fn parse_constraint(input: &str) -> IResult<&str, ArraySize> {
-    // Annahne: N ist in jedem Fall durch usize begrenzt
     alt((
         map(tag("[]"), |_| ArraySize::Dynamic),
-        map(delimited(tag("[<="), digit1, tag("]")), |digits: &str| {
-            let casted = digits.parse().unwrap();
-            ArraySize::BoundDynamic(casted)
+        map_res(delimited(tag("[<="), digit1, tag("]")), |digits: &str| {
+            digits.parse().map(ArraySize::BoundDynamic)
         }),
-        map(delimited(tag("["), digit1, tag("]")), |digits: &str| {
-            let casted = digits.parse().unwrap();
-            ArraySize::Capacity(casted)
+        map_res(delimited(tag("["), digit1, tag("]")), |digits: &str| {
+            digits.parse().map(ArraySize::Capacity)
         }),
     ))(input)
 }
@@ -167,9 +212,12 @@ fn parse_initial_value<'a>(
     if optional_array_size.is_some() {
         Box::new(map(
             delimited(
-                tag("["),
-                separated_list0(tag(","), parse_initial_value(base_type, &None)),
-                tag("]"),
+                terminated(tag("["), space0),
+                terminated(
+                    separated_list0(array_separator, parse_initial_value(base_type, &None)),
+                    opt(array_separator),
+                ),
+                preceded(space0, tag("]")),
             ),
             InitialValue::Array,
         ))
@@ -195,6 +243,10 @@ fn parse_initial_value<'a>(
     }
 }
 
+fn array_separator(input: &str) -> IResult<&str, &str> {
+    delimited(space0, tag(","), space0)(input)
+}
+
 fn parse_bool_literal(input: &str) -> IResult<&str, BoolRepresentation> {
     alt((
         map(tag("true"), |_| BoolRepresentation::String(true)),
@@ -204,7 +256,7 @@ fn parse_bool_literal(input: &str) -> IResult<&str, BoolRepresentation> {
     ))(input)
 }
 
-fn parse_int_literal(input: &str) -> IResult<&str, IntRepresentation> {
+pub(super) fn parse_int_literal(input: &str) -> IResult<&str, IntRepresentation> {
     alt((
         hex_int_parser,
         oct_int_parser,
@@ -260,7 +312,7 @@ fn bin_int_parser(input: &str) -> IResult<&str, IntRepresentation> {
     )(input)
 }
 
-fn parse_quoted_string(input: &str) -> IResult<&str, String> {
+pub(super) fn parse_quoted_string(input: &str) -> IResult<&str, String> {
     alt((
         delimited(tag("\""), parse_inner_string('"'), tag("\"")),
         delimited(tag("'"), parse_inner_string('\''), tag("'")),
@@ -270,19 +322,53 @@ fn parse_quoted_string(input: &str) -> IResult<&str, String> {
 fn parse_inner_string(quote: char) -> impl FnMut(&str) -> IResult<&str, String> {
     move |input: &str| {
         let mut ret = String::new();
-        let mut skip_delimiter = false;
-        for (i, ch) in input.char_indices() {
-            if ch == '\\' && !skip_delimiter {
-                skip_delimiter = true;
-            } else if ch == quote && !skip_delimiter {
+        let mut chars = input.char_indices();
+        while let Some((i, ch)) = chars.next() {
+            if ch == quote {
                 return Ok((&input[i..], ret));
-            } else {
+            }
+            if ch != '\\' {
                 ret.push(ch);
-                skip_delimiter = false;
+                continue;
+            }
+            let escaped = match chars.next() {
+                Some((_, 'n')) => Some('\n'),
+                Some((_, 't')) => Some('\t'),
+                Some((_, 'r')) => Some('\r'),
+                Some((_, '0')) => Some('\0'),
+                Some((_, 'a')) => Some('\x07'),
+                Some((_, 'b')) => Some('\x08'),
+                Some((_, 'f')) => Some('\x0C'),
+                Some((_, 'v')) => Some('\x0B'),
+                Some((_, 'x')) => parse_escaped_code_point(&mut chars, 2),
+                Some((_, 'u')) => parse_escaped_code_point(&mut chars, 4),
+                Some((_, 'U')) => parse_escaped_code_point(&mut chars, 8),
+                Some((_, other)) => Some(other),
+                None => None,
+            };
+            match escaped {
+                Some(escaped) => ret.push(escaped),
+                None => {
+                    return Err(nom::Err::Error(nom::error::Error::new(
+                        &input[i..],
+                        nom::error::ErrorKind::Escaped,
+                    )))
+                }
             }
         }
-        Err(nom::Err::Incomplete(nom::Needed::Unknown))
+        Err(nom::Err::Error(nom::error::Error::new(
+            input,
+            nom::error::ErrorKind::Char,
+        )))
+    }
+}
+
+fn parse_escaped_code_point(chars: &mut std::str::CharIndices, digits: usize) -> Option<char> {
+    let hex: String = chars.take(digits).map(|(_, c)| c).collect();
+    if hex.len() != digits {
+        return None;
     }
+    u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32)
 }
 
 fn bin_digit(input: &str) -> IResult<&str, &str> {
@@ -294,13 +380,10 @@ where
     P: Fn(&str) -> IResult<&str, &str>,
 {
     move |input: &str| {
-        let mut i = 0;
-        while i < input.len() {
-            let slice = &input[i..];
-            if end_parser(slice).is_ok() {
+        for (i, _) in input.char_indices() {
+            if end_parser(&input[i..]).is_ok() {
                 return Ok((&input[i..], &input[0..i]));
             }
-            i += 1;
         }
         Ok(("", input))
     }
@@ -0,0 +1,107 @@
+use std::borrow::Cow;
+
+use xmltree::{Element, EmitterConfig, XMLNode};
+
+use crate::business::error::Result;
+use crate::business::file_writer;
+use crate::core::dtp::{
+    XML_ATTRIBUTE_COMMENT, XML_ATTRIBUTE_NAME, XML_ATTRIBUTE_VERSION, XML_TAG_VERSION_INFO,
+};
+use crate::core::typelib::*;
+
+pub fn write(manifest: &Manifest, to_directory: &str) -> Result<()> {
+    write_element(
+        create_manifest_element(manifest),
+        &format!("{to_directory}{MANIFEST_FILE}"),
+    )?;
+    write_element(
+        create_project_description_element(manifest),
+        &format!("{to_directory}{PROJECT_FILE}"),
+    )?;
+    Ok(())
+}
+
+fn write_element(element: Element, path_to_file: &str) -> Result<()> {
+    let mut config = EmitterConfig::new();
+    config.perform_escaping = false;
+    config.perform_indent = true;
+    config.indent_string = Cow::Borrowed("    ");
+    config.pad_self_closing = false;
+    let mut content = Vec::new();
+    element.write_with_config(&mut content, config)?;
+    file_writer::write_if_changed(path_to_file, content)?;
+    Ok(())
+}
+
+fn create_manifest_element(manifest: &Manifest) -> Element {
+    let mut manifest_element = Element::new(XML_TAG_MANIFEST);
+    manifest_element.attributes.insert(
+        XML_ATTRIBUTE_SCOPE.to_string(),
+        XML_VALUE_LIBRARY_SCOPE.to_string(),
+    );
+    let mut product_element = Element::new(XML_TAG_PRODUCT);
+    product_element
+        .attributes
+        .insert(XML_ATTRIBUTE_NAME.to_string(), manifest.name().to_string());
+    product_element.attributes.insert(
+        XML_ATTRIBUTE_SYMBOLIC_NAME.to_string(),
+        manifest.symbolic_name().to_string(),
+    );
+    if let Some(comment) = manifest.comment() {
+        product_element
+            .attributes
+            .insert(XML_ATTRIBUTE_COMMENT.to_string(), comment.clone());
+    }
+    let mut version_info_element = Element::new(XML_TAG_VERSION_INFO);
+    version_info_element.attributes.insert(
+        XML_ATTRIBUTE_VERSION.to_string(),
+        manifest.version().to_string(),
+    );
+    product_element
+        .children
+        .push(XMLNode::Element(version_info_element));
+    manifest_element
+        .children
+        .push(XMLNode::Element(product_element));
+    manifest_element
+        .children
+        .push(XMLNode::Element(Element::new(XML_TAG_DEPENDENCIES)));
+    manifest_element
+}
+
+// the Eclipse project description which lets 4diac IDE import the library as a project
+fn create_project_description_element(manifest: &Manifest) -> Element {
+    let mut project_description_element = Element::new(XML_TAG_PROJECT_DESCRIPTION);
+    project_description_element
+        .children
+        .push(create_text_element(XML_TAG_NAME, manifest.symbolic_name()));
+    project_description_element
+        .children
+        .push(create_text_element(
+            XML_TAG_COMMENT,
+            manifest.comment().map(String::as_str).unwrap_or_default(),
+        ));
+    project_description_element
+        .children
+        .push(XMLNode::Element(Element::new(XML_TAG_PROJECTS)));
+    project_description_element
+        .children
+        .push(XMLNode::Element(Element::new(XML_TAG_BUILD_SPEC)));
+    let mut natures_element = Element::new(XML_TAG_NATURES);
+    natures_element.children.push(create_text_element(
+        XML_TAG_NATURE,
+        XML_VALUE_FORDIAC_NATURE,
+    ));
+    project_description_element
+        .children
+        .push(XMLNode::Element(natures_element));
+    project_description_element
+}
+
+fn create_text_element(tag: &str, text: &str) -> XMLNode {
+    let mut element = Element::new(tag);
+    if !text.is_empty() {
+        element.children.push(XMLNode::Text(text.to_string()));
+    }
+    XMLNode::Element(element)
+}
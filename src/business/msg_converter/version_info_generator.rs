@@ -0,0 +1,50 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+
+use chrono::{DateTime, Local};
+use sha2::{Digest, Sha256};
+
+use crate::business::error::{Error, Result};
+use crate::core::dtp;
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+// the reproducible builds convention for pinning timestamps in generated files
+const SOURCE_DATE_EPOCH: &str = "SOURCE_DATE_EPOCH";
+
+pub fn generate(
+    author: Option<&str>,
+    organization: Option<&str>,
+    version: &str,
+    path_to_source_file: &str,
+) -> Result<dtp::VersionInfo> {
+    let source_file_name = Path::new(path_to_source_file)
+        .file_name()
+        .map(|file_name| file_name.to_string_lossy().to_string())
+        .unwrap_or_else(|| path_to_source_file.to_string());
+    let hash = Sha256::digest(fs::read(path_to_source_file)?);
+    Ok(dtp::VersionInfo::new(
+        organization.map(str::to_string),
+        Some(version.to_string()),
+        author.map(str::to_string),
+        Some(generate_date(path_to_source_file)?),
+        Some(format!(
+            "Generated by RossyDiac from {source_file_name} (SHA-256 {hash:x})"
+        )),
+    ))
+}
+
+// The date of the last change of the source file keeps unchanged types from being written again
+fn generate_date(path_to_source_file: &str) -> Result<String> {
+    let date = match env::var(SOURCE_DATE_EPOCH) {
+        Ok(seconds) => seconds
+            .parse()
+            .ok()
+            .and_then(|seconds| DateTime::from_timestamp(seconds, 0))
+            .ok_or_else(|| Error::custom(format!("{SOURCE_DATE_EPOCH} is not a valid timestamp")))?
+            .date_naive(),
+        Err(_) => DateTime::<Local>::from(fs::metadata(path_to_source_file)?.modified()?)
+            .date_naive(),
+    };
+    Ok(date.format(DATE_FORMAT).to_string())
+}
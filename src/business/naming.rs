@@ -0,0 +1,198 @@
+const ROS2_KEYWORD_ESCAPE_SUFFIX: &str = "_field";
+const IEC61131_KEYWORD_ESCAPE_PREFIX: &str = "_";
+// IDL readers drop a leading underscore, so that identifiers can be escaped like this
+const IDL_KEYWORD_ESCAPE_PREFIX: &str = "_";
+
+// Keywords of the languages the rosidl generators emit code for
+const CPP_KEYWORDS: &[&str] = &[
+    "alignas", "alignof", "and", "and_eq", "asm", "auto", "bitand", "bitor", "bool", "break",
+    "case", "catch", "char", "char8_t", "char16_t", "char32_t", "class", "compl", "concept",
+    "const", "consteval", "constexpr", "constinit", "const_cast", "continue", "co_await",
+    "co_return", "co_yield", "decltype", "default", "delete", "do", "double", "dynamic_cast",
+    "else", "enum", "explicit", "export", "extern", "false", "float", "for", "friend", "goto",
+    "if", "inline", "int", "long", "mutable", "namespace", "new", "noexcept", "not", "not_eq",
+    "nullptr", "operator", "or", "or_eq", "private", "protected", "public", "register",
+    "reinterpret_cast", "requires", "return", "short", "signed", "sizeof", "static",
+    "static_assert", "static_cast", "struct", "switch", "template", "this", "thread_local",
+    "throw", "true", "try", "typedef", "typeid", "typename", "union", "unsigned", "using",
+    "virtual", "void", "volatile", "wchar_t", "while", "xor", "xor_eq",
+];
+const PYTHON_KEYWORDS: &[&str] = &[
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class",
+    "continue", "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if",
+    "import", "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try",
+    "while", "with", "yield",
+];
+// Reserved words of IEC 61131-3 structured text, i.e. the keywords of statements and
+// declarations, the operators and the elementary types, matched case-insensitively. Words only
+// reserved elsewhere, e.g. STEP, ON, PRIORITY and SINGLE in SFC and configurations, AT, TO and
+// the date and time types are common field names and are therefore kept.
+const IEC61131_KEYWORDS: &[&str] = &[
+    "AND", "ARRAY", "BOOL", "BYTE", "CASE", "CHAR", "CONSTANT", "CONTINUE", "DINT", "DO", "DWORD",
+    "ELSE", "ELSIF", "END_CASE", "END_FOR", "END_FUNCTION", "END_FUNCTION_BLOCK", "END_IF",
+    "END_PROGRAM", "END_REPEAT", "END_STRUCT", "END_TYPE", "END_VAR", "END_WHILE", "EXIT",
+    "FALSE", "FOR", "FUNCTION", "FUNCTION_BLOCK", "IF", "INT", "LINT", "LREAL", "LWORD", "MOD",
+    "NOT", "OF", "OR", "PROGRAM", "REAL", "REF_TO", "REPEAT", "RETAIN", "RETURN", "SINT",
+    "STRING", "STRUCT", "THEN", "TRUE", "TYPE", "UDINT", "UINT", "ULINT", "UNTIL", "USINT",
+    "VAR", "VAR_EXTERNAL", "VAR_GLOBAL", "VAR_INPUT", "VAR_IN_OUT", "VAR_OUTPUT", "VAR_TEMP",
+    "WCHAR", "WHILE", "WORD", "WSTRING", "XOR",
+];
+// Keywords of OMG IDL 4.2, which identifiers must not equal case-insensitively either
+const IDL_KEYWORDS: &[&str] = &[
+    "abstract", "alias", "any", "attribute", "bitfield", "bitmask", "bitset", "boolean", "case",
+    "char", "component", "connector", "const", "consumes", "context", "custom", "default",
+    "double", "emits", "enum", "eventtype", "exception", "factory", "false", "finder", "fixed",
+    "float", "getraises", "home", "import", "in", "inout", "int16", "int32", "int64", "int8",
+    "interface", "local", "long", "manages", "map", "mirrorport", "module", "multiple", "native",
+    "object", "octet", "oneway", "out", "port", "porttype", "primarykey", "private", "provides",
+    "public", "publishes", "raises", "readonly", "sequence", "setraises", "short", "string",
+    "struct", "supports", "switch", "true", "truncatable", "typedef", "typeid", "typename",
+    "typeprefix", "uint16", "uint32", "uint64", "uint8", "union", "unsigned", "uses",
+    "valuebase", "valuetype", "void", "wchar", "wstring",
+];
+
+pub fn to_ros2_field_name(iec_name: &str) -> String {
+    if follows_ros2_convention(iec_name, char::is_ascii_lowercase) {
+        return iec_name.to_string();
+    }
+    prefix_if_not_alphabetic(split_words(iec_name).join("_").to_lowercase(), "field")
+}
+
+pub fn to_ros2_constant_name(iec_name: &str) -> String {
+    if follows_ros2_convention(iec_name, char::is_ascii_uppercase) {
+        return iec_name.to_string();
+    }
+    prefix_if_not_alphabetic(split_words(iec_name).join("_").to_uppercase(), "CONSTANT")
+}
+
+// Conforming names are kept, since splitting them again would e.g. turn "A_4B" into "A_4_B"
+fn follows_ros2_convention(name: &str, is_letter: fn(&char) -> bool) -> bool {
+    name.starts_with(|c: char| is_letter(&c))
+        && name
+            .chars()
+            .all(|c| is_letter(&c) || c.is_ascii_digit() || c == '_')
+        && !name.contains("__")
+        && !name.ends_with('_')
+}
+
+// rosidl names the generated C++ headers after the snake case type name, e.g. "Ros2Test" -> "ros2_test"
+pub fn to_ros2_header_name(type_name: &str) -> String {
+    split_words(type_name).join("_").to_lowercase()
+}
+
+// The prefix alone is used for names without any letter or digit, e.g. "_", since ROS 2 names
+// must neither be empty nor end with an underscore
+fn prefix_if_not_alphabetic(name: String, prefix: &str) -> String {
+    let name = name.trim_end_matches('_');
+    if name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        name.to_string()
+    } else if name.is_empty() {
+        prefix.to_string()
+    } else {
+        format!("{prefix}_{name}")
+    }
+}
+
+// Splits at underscores and at camel case boundaries, e.g. "XMLParser_v2" -> ["XML", "Parser", "v2"]
+fn split_words(name: &str) -> Vec<String> {
+    let chars: Vec<char> = name.chars().collect();
+    let mut words = Vec::new();
+    let mut word = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if !c.is_ascii_alphanumeric() {
+            if !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+            continue;
+        }
+        let previous = i.checked_sub(1).map(|j| chars[j]);
+        let next = chars.get(i + 1);
+        let is_word_start = c.is_ascii_uppercase()
+            && (previous.is_some_and(|p| p.is_ascii_lowercase() || p.is_ascii_digit())
+                || (previous.is_some_and(|p| p.is_ascii_uppercase())
+                    && next.is_some_and(|n| n.is_ascii_lowercase())));
+        if is_word_start && !word.is_empty() {
+            words.push(std::mem::take(&mut word));
+        }
+        word.push(c);
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+pub fn escape_ros2_keyword(name: String) -> String {
+    if is_ros2_keyword(&name) {
+        format!("{name}{ROS2_KEYWORD_ESCAPE_SUFFIX}")
+    } else {
+        name
+    }
+}
+
+pub fn escape_iec61131_keyword(name: &str) -> String {
+    if is_iec61131_keyword(name) {
+        format!("{IEC61131_KEYWORD_ESCAPE_PREFIX}{name}")
+    } else {
+        name.to_string()
+    }
+}
+
+pub fn escape_idl_keyword(name: &str) -> String {
+    if IDL_KEYWORDS
+        .iter()
+        .any(|keyword| keyword.eq_ignore_ascii_case(name))
+    {
+        format!("{IDL_KEYWORD_ESCAPE_PREFIX}{name}")
+    } else {
+        name.to_string()
+    }
+}
+
+pub fn is_ros2_keyword(name: &str) -> bool {
+    CPP_KEYWORDS.contains(&name) || PYTHON_KEYWORDS.contains(&name)
+}
+
+pub fn is_iec61131_keyword(name: &str) -> bool {
+    IEC61131_KEYWORDS
+        .iter()
+        .any(|keyword| keyword.eq_ignore_ascii_case(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn field_names_neither_end_with_an_underscore_nor_are_empty() {
+        assert_eq!(to_ros2_field_name("A_"), "a");
+        assert_eq!(to_ros2_field_name("_"), "field");
+        assert_eq!(to_ros2_field_name("___"), "field");
+        assert_eq!(to_ros2_field_name("_1_"), "field_1");
+        assert_eq!(to_ros2_field_name("Ä"), "field");
+    }
+
+    #[test]
+    fn only_reserved_iec61131_words_are_escaped() {
+        for name in ["time", "step", "on", "at", "to", "date", "priority", "single", "by"] {
+            assert_eq!(escape_iec61131_keyword(name), name);
+        }
+        for name in ["end_var", "int", "mod", "String", "IF"] {
+            assert_eq!(escape_iec61131_keyword(name), format!("_{name}"));
+        }
+    }
+
+    #[test]
+    fn idl_keywords_are_escaped() {
+        assert_eq!(escape_idl_keyword("module"), "_module");
+        assert_eq!(escape_idl_keyword("Port"), "_Port");
+        assert_eq!(escape_idl_keyword("position"), "position");
+    }
+
+    #[test]
+    fn constant_names_neither_end_with_an_underscore_nor_are_empty() {
+        assert_eq!(to_ros2_constant_name("a_"), "A");
+        assert_eq!(to_ros2_constant_name("_"), "CONSTANT");
+        assert_eq!(to_ros2_constant_name("_2"), "CONSTANT_2");
+    }
+}
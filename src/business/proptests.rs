@@ -0,0 +1,552 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
+
+use proptest::prelude::*;
+
+use crate::business::dtp_converter::converter::{self as dtp_converter, ArrayPadding};
+use crate::business::dtp_converter::{dtp_reader, msg_writer};
+use crate::business::msg_converter::converter::{self as msg_converter, TypeNaming};
+use crate::business::msg_converter::{dtp_writer, msg_reader};
+use crate::business::{naming, roundtrip};
+use crate::core::{dtp, msg};
+
+const PACKAGE_NAME: &str = "proptest_msgs";
+
+proptest! {
+    #[test]
+    fn msg_write_read_is_identity(structured_type in msg_structured_type(package())) {
+        let directory = test_directory("msg_write_read");
+        msg_writer::write(&structured_type, &directory).unwrap();
+        let read = msg_reader::read(&format!("{directory}{}.msg", structured_type.name())).unwrap();
+        prop_assert_eq!(read, structured_type);
+    }
+
+    #[test]
+    fn dtp_write_read_is_identity(data_type in dtp_data_type()) {
+        let directory = test_directory("dtp_write_read");
+        dtp_writer::write(data_type.clone(), &directory).unwrap();
+        let read = dtp_reader::read(&format!("{directory}{}.dtp", data_type.name())).unwrap();
+        prop_assert_eq!(read, data_type);
+    }
+
+    #[test]
+    fn msg_to_mangled_dtp_to_msg_preserves_semantics(
+        // mangled names cannot tell packages apart, so references stay within the package
+        structured_type in msg_structured_type(Just(PACKAGE_NAME.to_string()))
+    ) {
+        assert_msg_round_trip(&structured_type, TypeNaming::Mangled)?;
+    }
+
+    #[test]
+    fn msg_to_packaged_dtp_to_msg_preserves_semantics(
+        structured_type in msg_structured_type(package())
+    ) {
+        assert_msg_round_trip(&structured_type, TypeNaming::Packaged)?;
+    }
+}
+
+fn assert_msg_round_trip(
+    structured_type: &msg::StructuredType,
+    type_naming: TypeNaming,
+) -> std::result::Result<(), TestCaseError> {
+    let version_info = dtp::VersionInfo::new(None, None, None, None, None);
+    let data_type =
+        msg_converter::convert(PACKAGE_NAME, structured_type, &version_info, type_naming).unwrap();
+    let round_tripped =
+        dtp_converter::convert(PACKAGE_NAME, &data_type, ArrayPadding::Reject).unwrap();
+    let differences = roundtrip::diff_msg(structured_type, &round_tripped);
+    prop_assert!(
+        differences.is_empty(),
+        "{}",
+        differences
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("\n")
+    );
+    Ok(())
+}
+
+fn test_directory(name: &str) -> String {
+    let directory: PathBuf = std::env::temp_dir()
+        .join(format!("rossydiac-{}", std::process::id()))
+        .join(name);
+    fs::create_dir_all(&directory).unwrap();
+    format!("{}/", directory.display())
+}
+
+// -- MSG
+
+// Packages may start like a primitive type, e.g. "char_msgs"
+fn package() -> impl Strategy<Value = String> {
+    "(bool|char|string|int8)?[a-z][a-z0-9_]{0,8}"
+}
+
+fn msg_structured_type(
+    package: impl Strategy<Value = String> + 'static,
+) -> impl Strategy<Value = msg::StructuredType> {
+    (
+        "[A-Z][A-Za-z0-9]{0,8}",
+        prop::collection::vec(prop_oneof![msg_variable(package), msg_constant()], 0..6),
+    )
+        .prop_map(|(name, mut fields)| {
+            // IEC 61131-3 names are case insensitive
+            let mut names = HashSet::new();
+            fields.retain(|field| names.insert(field.name().to_lowercase()));
+            msg::StructuredType::new(name, fields)
+        })
+}
+
+fn msg_variable(
+    package: impl Strategy<Value = String> + 'static,
+) -> impl Strategy<Value = msg::Field> {
+    (
+        "[a-z][a-z0-9]{0,5}(_[a-z0-9]{1,4}){0,2}".prop_filter("ROS 2 rejects keywords", |name| {
+            !naming::is_ros2_keyword(name)
+        }),
+        msg_base_type(package),
+        prop::option::of(msg_array_size()),
+        prop::option::of(msg_comment()),
+    )
+        .prop_flat_map(|(name, base_type, array_size, comment)| {
+            let initial_value = match base_type {
+                msg::BaseType::Custom(_) => Just(None).boxed(),
+                _ => prop::option::of(msg_initial_value(&base_type, array_size.as_ref())).boxed(),
+            };
+            initial_value.prop_map(move |initial_value| {
+                msg::Field::new(
+                    name.clone(),
+                    base_type.clone(),
+                    array_size.clone(),
+                    msg::FieldType::Variable,
+                    initial_value,
+                    comment.clone(),
+                    Vec::new(),
+                )
+            })
+        })
+}
+
+fn msg_constant() -> impl Strategy<Value = msg::Field> {
+    (
+        "[A-Z][A-Z0-9]{0,5}(_[A-Z0-9]{1,4}){0,2}",
+        msg_primitive_type(),
+        prop::option::of(msg_comment()),
+    )
+        .prop_flat_map(|(name, base_type, comment)| {
+            msg_element_value(&base_type).prop_map(move |initial_value| {
+                msg::Field::new(
+                    name.clone(),
+                    base_type.clone(),
+                    None,
+                    msg::FieldType::Constant,
+                    Some(initial_value),
+                    comment.clone(),
+                    Vec::new(),
+                )
+            })
+        })
+}
+
+// Comments starting with "@" carry metadata, which is covered by the converter
+fn msg_comment() -> impl Strategy<Value = String> {
+    "[A-Za-z0-9]([ -~]{0,20}[!-~])?"
+}
+
+fn msg_primitive_type() -> impl Strategy<Value = msg::BaseType> {
+    prop_oneof![
+        Just(msg::BaseType::Bool),
+        Just(msg::BaseType::Byte),
+        Just(msg::BaseType::Uint8),
+        Just(msg::BaseType::Uint16),
+        Just(msg::BaseType::Uint32),
+        Just(msg::BaseType::Uint64),
+        Just(msg::BaseType::Int8),
+        Just(msg::BaseType::Int16),
+        Just(msg::BaseType::Int32),
+        Just(msg::BaseType::Int64),
+        Just(msg::BaseType::Float32),
+        Just(msg::BaseType::Float64),
+        Just(msg::BaseType::Char),
+        Just(msg::BaseType::String(None)),
+        Just(msg::BaseType::Wstring(None)),
+    ]
+}
+
+fn msg_base_type(
+    package: impl Strategy<Value = String> + 'static,
+) -> impl Strategy<Value = msg::BaseType> {
+    prop_oneof![
+        4 => msg_primitive_type(),
+        1 => (1..20u64).prop_map(|bound| msg::BaseType::String(Some(bound))),
+        1 => (1..20u64).prop_map(|bound| msg::BaseType::Wstring(Some(bound))),
+        1 => msg_reference(package).prop_map(msg::BaseType::Custom),
+    ]
+}
+
+fn msg_reference(
+    package: impl Strategy<Value = String> + 'static,
+) -> impl Strategy<Value = msg::Reference> {
+    prop_oneof![
+        "[A-Z][A-Za-z0-9]{0,8}".prop_map(|file| msg::Reference::Relative { file }),
+        (package, "[A-Z][A-Za-z0-9]{0,8}")
+            .prop_map(|(package, file)| msg::Reference::Absolute { package, file }),
+    ]
+}
+
+fn msg_array_size() -> impl Strategy<Value = msg::ArraySize> {
+    prop_oneof![
+        (1..5u64).prop_map(msg::ArraySize::Capacity),
+        Just(msg::ArraySize::Dynamic),
+        (1..5u64).prop_map(msg::ArraySize::BoundDynamic),
+    ]
+}
+
+fn msg_initial_value(
+    base_type: &msg::BaseType,
+    array_size: Option<&msg::ArraySize>,
+) -> BoxedStrategy<msg::InitialValue> {
+    let element = msg_element_value(base_type);
+    match array_size {
+        // static arrays are completely initialized, partial ones are padded by the converter
+        Some(msg::ArraySize::Capacity(capacity)) => {
+            prop::collection::vec(element, *capacity as usize)
+                .prop_map(msg::InitialValue::Array)
+                .boxed()
+        }
+        Some(msg::ArraySize::Dynamic) => prop::collection::vec(element, 0..5)
+            .prop_map(msg::InitialValue::Array)
+            .boxed(),
+        Some(msg::ArraySize::BoundDynamic(bound)) => {
+            prop::collection::vec(element, 0..=*bound as usize)
+                .prop_map(msg::InitialValue::Array)
+                .boxed()
+        }
+        None => msg_element_value(base_type),
+    }
+}
+
+fn msg_element_value(base_type: &msg::BaseType) -> BoxedStrategy<msg::InitialValue> {
+    match base_type {
+        msg::BaseType::Bool => prop_oneof![
+            any::<bool>().prop_map(msg::BoolRepresentation::String),
+            any::<bool>().prop_map(msg::BoolRepresentation::Binary),
+        ]
+        .prop_map(msg::InitialValue::Bool)
+        .boxed(),
+        msg::BaseType::Byte => msg_int(0, u8::MAX.into(), msg::InitialValue::Byte),
+        msg::BaseType::Uint8 => msg_int(0, u8::MAX.into(), msg::InitialValue::Uint8),
+        msg::BaseType::Uint16 => msg_int(0, u16::MAX.into(), msg::InitialValue::Uint16),
+        msg::BaseType::Uint32 => msg_int(0, u32::MAX.into(), msg::InitialValue::Uint32),
+        msg::BaseType::Uint64 => msg_int(0, u64::MAX, msg::InitialValue::Uint64),
+        msg::BaseType::Int8 => msg_int(i8::MIN.into(), i8::MAX as u64, msg::InitialValue::Int8),
+        msg::BaseType::Int16 => msg_int(i16::MIN.into(), i16::MAX as u64, msg::InitialValue::Int16),
+        msg::BaseType::Int32 => msg_int(i32::MIN.into(), i32::MAX as u64, msg::InitialValue::Int32),
+        msg::BaseType::Int64 => msg_int(i64::MIN, i64::MAX as u64, msg::InitialValue::Int64),
+        msg::BaseType::Char => msg_int(0, u8::MAX.into(), msg::InitialValue::Char),
+        msg::BaseType::Float32 => (prop::num::f32::NORMAL | prop::num::f32::ZERO)
+            .prop_map(msg::InitialValue::Float32)
+            .boxed(),
+        msg::BaseType::Float64 => (prop::num::f64::NORMAL | prop::num::f64::ZERO)
+            .prop_map(msg::InitialValue::Float64)
+            .boxed(),
+        msg::BaseType::String(bound) => msg_string(*bound)
+            .prop_map(msg::InitialValue::String)
+            .boxed(),
+        msg::BaseType::Wstring(bound) => msg_string(*bound)
+            .prop_map(msg::InitialValue::Wstring)
+            .boxed(),
+        msg::BaseType::Custom(_) => unreachable!("custom types have no literals"),
+    }
+}
+
+fn msg_string(bound: Option<u64>) -> impl Strategy<Value = String> {
+    prop::collection::vec(text_char(), 0..=bound.unwrap_or(10) as usize)
+        .prop_map(|chars| chars.into_iter().collect())
+}
+
+// Positive values are written without sign, so they are read as unsigned decimals
+fn msg_int(
+    min: i64,
+    max: u64,
+    initial_value: fn(msg::IntRepresentation) -> msg::InitialValue,
+) -> BoxedStrategy<msg::InitialValue> {
+    let unsigned = prop_oneof![
+        (0..=max).prop_map(msg::IntRepresentation::UnsignedDecimal),
+        (0..=max).prop_map(msg::IntRepresentation::Binary),
+        (0..=max).prop_map(msg::IntRepresentation::Octal),
+        (0..=max).prop_map(msg::IntRepresentation::Hexadecimal),
+    ];
+    if min < 0 {
+        prop_oneof![
+            (min..0).prop_map(msg::IntRepresentation::SignedDecimal),
+            unsigned,
+        ]
+        .prop_map(initial_value)
+        .boxed()
+    } else {
+        unsigned.prop_map(initial_value).boxed()
+    }
+}
+
+// -- DTP
+
+fn dtp_data_type() -> impl Strategy<Value = dtp::DataType> {
+    (
+        "[A-Za-z][A-Za-z0-9_]{0,10}",
+        prop::option::of(xml_text()),
+        prop::option::of(dtp_identification()),
+        prop::collection::vec(dtp_version_info(), 0..3),
+        prop::option::of(dtp_compiler_info()),
+        prop::option::of(xml_text()),
+        prop::collection::vec(dtp_var_declaration(), 0..6),
+    )
+        .prop_map(
+            |(
+                name,
+                comment,
+                identification,
+                version_infos,
+                compiler_info,
+                structured_type_comment,
+                var_declarations,
+            )| {
+                dtp::DataType::new(
+                    name,
+                    comment,
+                    identification,
+                    version_infos,
+                    compiler_info,
+                    dtp::StructuredType::new(structured_type_comment, var_declarations),
+                )
+            },
+        )
+}
+
+fn dtp_identification() -> impl Strategy<Value = dtp::Identification> {
+    prop::array::uniform6(prop::option::of(xml_text())).prop_map(
+        |[standard, classification, application_domain, function, identification_type, description]| {
+            dtp::Identification::new(
+                standard,
+                classification,
+                application_domain,
+                function,
+                identification_type,
+                description,
+            )
+        },
+    )
+}
+
+fn dtp_version_info() -> impl Strategy<Value = dtp::VersionInfo> {
+    prop::array::uniform5(prop::option::of(xml_text())).prop_map(
+        |[organization, version, author, date, remarks]| {
+            dtp::VersionInfo::new(organization, version, author, date, remarks)
+        },
+    )
+}
+
+fn dtp_compiler_info() -> impl Strategy<Value = dtp::CompilerInfo> {
+    (
+        prop::array::uniform3(prop::option::of(xml_text())),
+        prop::collection::vec(
+            prop::array::uniform4(prop::option::of(xml_text())).prop_map(
+                |[language, vendor, product, version]| {
+                    dtp::Compiler::new(language, vendor, product, version)
+                },
+            ),
+            0..3,
+        ),
+    )
+        .prop_map(|([package_name, header, classdef], compilers)| {
+            dtp::CompilerInfo::new(package_name, header, classdef, compilers)
+        })
+}
+
+fn dtp_var_declaration() -> impl Strategy<Value = dtp::VarDeclaration> {
+    (
+        "[A-Za-z][A-Za-z0-9_]{0,8}",
+        dtp_base_type(),
+        prop::option::of(dtp_array_size()),
+        prop::option::of(xml_text()),
+        prop::collection::vec(dtp_attribute(), 0..3),
+    )
+        .prop_flat_map(|(name, base_type, array_size, comment, attributes)| {
+            let initial_value = match base_type {
+                dtp::BaseType::Custom(_) => Just(None).boxed(),
+                _ => prop::option::of(dtp_initial_value(&base_type, array_size.is_some())).boxed(),
+            };
+            initial_value.prop_map(move |initial_value| {
+                dtp::VarDeclaration::new(
+                    name.clone(),
+                    base_type.clone(),
+                    array_size.clone(),
+                    initial_value,
+                    comment.clone(),
+                    attributes.clone(),
+                )
+            })
+        })
+}
+
+fn dtp_attribute() -> impl Strategy<Value = dtp::Attribute> {
+    (
+        "[A-Za-z][A-Za-z0-9_]{0,8}",
+        dtp_elementary_type(),
+        prop::option::of(xml_text()),
+    )
+        .prop_flat_map(|(name, base_type, comment)| {
+            dtp_initial_value(&base_type, false).prop_map(move |value| {
+                dtp::Attribute::new(name.clone(), base_type.clone(), value, comment.clone())
+            })
+        })
+}
+
+fn dtp_elementary_type() -> impl Strategy<Value = dtp::BaseType> {
+    prop_oneof![
+        Just(dtp::BaseType::BOOL),
+        Just(dtp::BaseType::BYTE),
+        Just(dtp::BaseType::WORD),
+        Just(dtp::BaseType::DWORD),
+        Just(dtp::BaseType::LWORD),
+        Just(dtp::BaseType::SINT),
+        Just(dtp::BaseType::INT),
+        Just(dtp::BaseType::DINT),
+        Just(dtp::BaseType::LINT),
+        Just(dtp::BaseType::USINT),
+        Just(dtp::BaseType::UINT),
+        Just(dtp::BaseType::UDINT),
+        Just(dtp::BaseType::ULINT),
+        Just(dtp::BaseType::REAL),
+        Just(dtp::BaseType::LREAL),
+        Just(dtp::BaseType::CHAR),
+        prop::option::of(1..100u64).prop_map(dtp::BaseType::STRING),
+        prop::option::of(1..100u64).prop_map(dtp::BaseType::WSTRING),
+    ]
+}
+
+fn dtp_base_type() -> impl Strategy<Value = dtp::BaseType> {
+    prop_oneof![
+        4 => dtp_elementary_type(),
+        1 => dtp_type_name().prop_map(dtp::BaseType::Custom),
+    ]
+}
+
+// Unqualified names may start like an elementary type, e.g. "INTERVAL"
+fn dtp_type_name() -> impl Strategy<Value = dtp::TypeName> {
+    (
+        prop::option::of("[a-z][a-z0-9_]{0,5}(::[a-z][a-z0-9_]{0,5}){0,2}"),
+        "(BOOL|INT|STRING|WSTRING|REAL)?[A-Z][A-Za-z0-9_]{0,8}",
+    )
+        .prop_map(|(package, name)| dtp::TypeName::new(package, name))
+}
+
+fn dtp_array_size() -> impl Strategy<Value = dtp::ArraySize> {
+    prop_oneof![
+        (1..10u64).prop_map(dtp::ArraySize::Capacity),
+        (-10..10i64, 0..10i64)
+            .prop_map(|(start, length)| { dtp::ArraySize::Indexation(start, start + length) }),
+    ]
+}
+
+fn dtp_initial_value(
+    base_type: &dtp::BaseType,
+    is_array: bool,
+) -> BoxedStrategy<dtp::InitialValue> {
+    if is_array {
+        prop::collection::vec(dtp_initial_value(base_type, false), 0..5)
+            .prop_map(dtp::InitialValue::Array)
+            .boxed()
+    } else {
+        match base_type {
+            dtp::BaseType::BOOL => prop_oneof![
+                any::<bool>().prop_map(dtp::BoolRepresentation::String),
+                any::<bool>().prop_map(dtp::BoolRepresentation::Binary),
+            ]
+            .prop_map(dtp::InitialValue::BOOL)
+            .boxed(),
+            dtp::BaseType::BYTE => dtp_int().prop_map(dtp::InitialValue::BYTE).boxed(),
+            dtp::BaseType::WORD => dtp_int().prop_map(dtp::InitialValue::WORD).boxed(),
+            dtp::BaseType::DWORD => dtp_int().prop_map(dtp::InitialValue::DWORD).boxed(),
+            dtp::BaseType::LWORD => dtp_int().prop_map(dtp::InitialValue::LWORD).boxed(),
+            dtp::BaseType::SINT => dtp_int().prop_map(dtp::InitialValue::SINT).boxed(),
+            dtp::BaseType::INT => dtp_int().prop_map(dtp::InitialValue::INT).boxed(),
+            dtp::BaseType::DINT => dtp_int().prop_map(dtp::InitialValue::DINT).boxed(),
+            dtp::BaseType::LINT => dtp_int().prop_map(dtp::InitialValue::LINT).boxed(),
+            dtp::BaseType::USINT => dtp_int().prop_map(dtp::InitialValue::USINT).boxed(),
+            dtp::BaseType::UINT => dtp_int().prop_map(dtp::InitialValue::UINT).boxed(),
+            dtp::BaseType::UDINT => dtp_int().prop_map(dtp::InitialValue::UDINT).boxed(),
+            dtp::BaseType::ULINT => dtp_int().prop_map(dtp::InitialValue::ULINT).boxed(),
+            dtp::BaseType::REAL => (prop::num::f32::NORMAL | prop::num::f32::ZERO)
+                .prop_map(dtp::InitialValue::REAL)
+                .boxed(),
+            dtp::BaseType::LREAL => (prop::num::f64::NORMAL | prop::num::f64::ZERO)
+                .prop_map(dtp::InitialValue::LREAL)
+                .boxed(),
+            dtp::BaseType::CHAR => dtp_byte_char().prop_map(dtp::InitialValue::CHAR).boxed(),
+            dtp::BaseType::STRING(_) => prop::collection::vec(dtp_char(), 0..10)
+                .prop_map(dtp::InitialValue::STRING)
+                .boxed(),
+            dtp::BaseType::WSTRING(_) => prop::collection::vec(dtp_wchar(), 0..10)
+                .prop_map(dtp::InitialValue::WSTRING)
+                .boxed(),
+            dtp::BaseType::Custom(_) => unreachable!("custom types have no literals"),
+        }
+    }
+}
+
+// Positive values are written without sign, so they are read as unsigned decimals
+fn dtp_int() -> impl Strategy<Value = dtp::IntRepresentation> {
+    prop_oneof![
+        (i64::MIN..0).prop_map(dtp::IntRepresentation::SignedDecimal),
+        any::<u64>().prop_map(dtp::IntRepresentation::UnsignedDecimal),
+        any::<u64>().prop_map(dtp::IntRepresentation::Binary),
+        any::<u64>().prop_map(dtp::IntRepresentation::Octal),
+        any::<u64>().prop_map(dtp::IntRepresentation::Heaxdecimal),
+    ]
+}
+
+// Control characters without an escape sequence are written in hexadecimal notation
+fn dtp_char() -> impl Strategy<Value = dtp::CharRepresentation> {
+    prop_oneof![
+        text_char().prop_map(dtp::CharRepresentation::Char),
+        any::<u8>().prop_map(dtp::CharRepresentation::Hexadecimal),
+    ]
+}
+
+// A CHAR holds a single byte
+fn dtp_byte_char() -> impl Strategy<Value = dtp::CharRepresentation> {
+    prop_oneof![
+        prop::char::range(' ', '\u{FF}')
+            .prop_filter("no control characters", |char| !char.is_control())
+            .prop_map(dtp::CharRepresentation::Char),
+        any::<u8>().prop_map(dtp::CharRepresentation::Hexadecimal),
+    ]
+}
+
+fn dtp_wchar() -> impl Strategy<Value = dtp::WcharRepresentation> {
+    prop_oneof![
+        text_char().prop_map(dtp::WcharRepresentation::Wchar),
+        any::<u16>()
+            .prop_filter_map("surrogates are no characters", |u16| char::from_u32(
+                u16.into()
+            ))
+            .prop_map(dtp::WcharRepresentation::Hexadecimal),
+    ]
+}
+
+// Characters which are either printable or have an escape sequence in both formats
+fn text_char() -> impl Strategy<Value = char> {
+    prop_oneof![
+        4 => prop::char::range(' ', '\u{D7FF}').prop_filter("no control characters", |char| {
+            !char.is_control()
+        }),
+        1 => prop::sample::select(vec!['\n', '\r', '\t', '$', '\'', '"', '\\', '&', '<', '>']),
+    ]
+}
+
+// XML normalizes line breaks and tabs in attributes, so they are not part of the text
+fn xml_text() -> impl Strategy<Value = String> {
+    "[ -~]{0,20}"
+}
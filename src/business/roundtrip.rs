@@ -0,0 +1,339 @@
+use std::fmt;
+
+use crate::business::dtp_converter::converter as dtp_converter;
+use crate::business::error::Result;
+use crate::core::{dtp, msg};
+
+#[derive(Clone, Debug)]
+pub struct Difference {
+    path: String,
+    original: String,
+    round_tripped: String,
+}
+
+impl Difference {
+    fn new(path: String, original: String, round_tripped: String) -> Self {
+        Self {
+            path,
+            original,
+            round_tripped,
+        }
+    }
+}
+
+impl fmt::Display for Difference {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} became {}",
+            self.path, self.original, self.round_tripped
+        )
+    }
+}
+
+// Comments are not compared, they only carry documentation and the metadata which is compared
+// through the values it encodes
+pub fn diff_msg(
+    original: &msg::StructuredType,
+    round_tripped: &msg::StructuredType,
+) -> Vec<Difference> {
+    let mut differences = Vec::new();
+    compare(
+        &mut differences,
+        "name".to_string(),
+        original.name().to_string(),
+        round_tripped.name().to_string(),
+    );
+    compare(
+        &mut differences,
+        "fields".to_string(),
+        original.fields().len().to_string(),
+        round_tripped.fields().len().to_string(),
+    );
+    for (original, round_tripped) in original.fields().iter().zip(round_tripped.fields()) {
+        let path = format!("field {}", original.name());
+        compare(
+            &mut differences,
+            format!("{path} name"),
+            original.name().to_string(),
+            round_tripped.name().to_string(),
+        );
+        compare(
+            &mut differences,
+            format!("{path} field type"),
+            format!("{:?}", original.field_type()),
+            format!("{:?}", round_tripped.field_type()),
+        );
+        compare(
+            &mut differences,
+            format!("{path} base type"),
+            format!("{:?}", original.base_type()),
+            format!("{:?}", round_tripped.base_type()),
+        );
+        compare(
+            &mut differences,
+            format!("{path} array size"),
+            format!("{:?}", original.array_size()),
+            format!("{:?}", round_tripped.array_size()),
+        );
+        compare(
+            &mut differences,
+            format!("{path} initial value"),
+            optional_msg_value(original.initial_value()),
+            optional_msg_value(round_tripped.initial_value()),
+        );
+        compare(
+            &mut differences,
+            format!("{path} annotations"),
+            msg_annotations(original.annotations()),
+            msg_annotations(round_tripped.annotations()),
+        );
+    }
+    differences
+}
+
+pub fn diff_dtp(
+    module_name: &str,
+    original: &dtp::DataType,
+    round_tripped: &dtp::DataType,
+) -> Result<Vec<Difference>> {
+    let mut differences = Vec::new();
+    // the round trip may add the package prefix, so the names are compared without it
+    compare(
+        &mut differences,
+        "name".to_string(),
+        dtp_converter::convert_data_type_name(module_name, original)?,
+        dtp_converter::convert_data_type_name(module_name, round_tripped)?,
+    );
+    let original = original.structured_type().var_declarations();
+    let round_tripped = round_tripped.structured_type().var_declarations();
+    compare(
+        &mut differences,
+        "var declarations".to_string(),
+        original.len().to_string(),
+        round_tripped.len().to_string(),
+    );
+    for (original, round_tripped) in original.iter().zip(round_tripped) {
+        let path = format!("var declaration {}", original.name());
+        compare(
+            &mut differences,
+            format!("{path} name"),
+            original.name().to_string(),
+            round_tripped.name().to_string(),
+        );
+        compare(
+            &mut differences,
+            format!("{path} base type"),
+            dtp_base_type(module_name, original.base_type()),
+            dtp_base_type(module_name, round_tripped.base_type()),
+        );
+        compare(
+            &mut differences,
+            format!("{path} array size"),
+            format!("{:?}", original.array_size()),
+            format!("{:?}", round_tripped.array_size()),
+        );
+        compare(
+            &mut differences,
+            format!("{path} initial value"),
+            original
+                .initial_value()
+                .map(dtp_value)
+                .unwrap_or_else(|| "nothing".to_string()),
+            round_tripped
+                .initial_value()
+                .map(|initial_value| dtp_value_without_padding(original, initial_value))
+                .unwrap_or_else(|| "nothing".to_string()),
+        );
+        // the converter marks every reference it writes as relative or absolute
+        let has_reference_attribute = original.attributes().iter().any(is_reference_attribute);
+        compare(
+            &mut differences,
+            format!("{path} attributes"),
+            dtp_attributes(original.attributes()),
+            dtp_attributes(
+                round_tripped.attributes().iter().filter(|attribute| {
+                    has_reference_attribute || !is_reference_attribute(attribute)
+                }),
+            ),
+        );
+    }
+    Ok(differences)
+}
+
+fn compare(
+    differences: &mut Vec<Difference>,
+    path: String,
+    original: String,
+    round_tripped: String,
+) {
+    if original != round_tripped {
+        differences.push(Difference::new(path, original, round_tripped));
+    }
+}
+
+// Values are rendered canonically, so that e.g. 0x10 and 16 or TRUE and 1 are considered equal
+pub(crate) fn optional_msg_value(initial_value: Option<&msg::InitialValue>) -> String {
+    initial_value
+        .map(msg_value)
+        .unwrap_or_else(|| "nothing".to_string())
+}
+
+fn msg_value(initial_value: &msg::InitialValue) -> String {
+    match initial_value {
+        msg::InitialValue::Bool(msg::BoolRepresentation::String(bool))
+        | msg::InitialValue::Bool(msg::BoolRepresentation::Binary(bool)) => bool.to_string(),
+        msg::InitialValue::Byte(int_representation)
+        | msg::InitialValue::Uint8(int_representation)
+        | msg::InitialValue::Uint16(int_representation)
+        | msg::InitialValue::Uint32(int_representation)
+        | msg::InitialValue::Uint64(int_representation)
+        | msg::InitialValue::Int8(int_representation)
+        | msg::InitialValue::Int16(int_representation)
+        | msg::InitialValue::Int32(int_representation)
+        | msg::InitialValue::Int64(int_representation)
+        | msg::InitialValue::Char(int_representation) => match int_representation {
+            msg::IntRepresentation::SignedDecimal(i64) => i64.to_string(),
+            msg::IntRepresentation::UnsignedDecimal(u64)
+            | msg::IntRepresentation::Binary(u64)
+            | msg::IntRepresentation::Octal(u64)
+            | msg::IntRepresentation::Hexadecimal(u64) => u64.to_string(),
+        },
+        msg::InitialValue::Float32(f32) => f32.to_string(),
+        msg::InitialValue::Float64(f64) => f64.to_string(),
+        msg::InitialValue::String(string) | msg::InitialValue::Wstring(string) => {
+            format!("{string:?}")
+        }
+        msg::InitialValue::Array(values) => format!(
+            "[{}]",
+            values.iter().map(msg_value).collect::<Vec<_>>().join(", ")
+        ),
+    }
+}
+
+fn msg_annotations(annotations: &[msg::Annotation]) -> String {
+    let annotations: Vec<String> = annotations
+        .iter()
+        .map(|annotation| match annotation {
+            msg::Annotation::Key => "key".to_string(),
+            msg::Annotation::Unit(unit) => format!("unit {unit:?}"),
+            msg::Annotation::Range { min, max } => format!(
+                "range {}..{}",
+                optional_msg_value(min.as_ref()),
+                optional_msg_value(max.as_ref())
+            ),
+            msg::Annotation::Default(initial_value) => {
+                format!("default {}", msg_value(initial_value))
+            }
+            msg::Annotation::Verbatim { language, text } => {
+                format!("verbatim {language:?} {text:?}")
+            }
+        })
+        .collect();
+    format!("[{}]", annotations.join(", "))
+}
+
+// Custom types are compared without the package prefix the round trip may add
+fn dtp_base_type(module_name: &str, base_type: &dtp::BaseType) -> String {
+    match base_type {
+        dtp::BaseType::Custom(type_name) if type_name.package().is_none() => format!(
+            "Custom({})",
+            dtp_converter::strip_mangled_prefix(module_name, type_name.name())
+        ),
+        _ => format!("{base_type:?}"),
+    }
+}
+
+// Padding partial static arrays appends default elements, which do not change the meaning
+fn dtp_value_without_padding(
+    original: &dtp::VarDeclaration,
+    round_tripped: &dtp::InitialValue,
+) -> String {
+    if let (Some(dtp::InitialValue::Array(original_values)), dtp::InitialValue::Array(values)) =
+        (original.initial_value(), round_tripped)
+    {
+        if let Ok(filler) =
+            dtp_converter::create_filler_initial_value(original, original_values.first())
+        {
+            let filler = dtp_value(&filler);
+            let mut length = values.len();
+            while length > original_values.len() && dtp_value(&values[length - 1]) == filler {
+                length -= 1;
+            }
+            return dtp_value(&dtp::InitialValue::Array(values[..length].to_vec()));
+        }
+    }
+    dtp_value(round_tripped)
+}
+
+fn dtp_value(initial_value: &dtp::InitialValue) -> String {
+    match initial_value {
+        dtp::InitialValue::BOOL(dtp::BoolRepresentation::String(bool))
+        | dtp::InitialValue::BOOL(dtp::BoolRepresentation::Binary(bool)) => bool.to_string(),
+        dtp::InitialValue::BYTE(int_representation)
+        | dtp::InitialValue::WORD(int_representation)
+        | dtp::InitialValue::DWORD(int_representation)
+        | dtp::InitialValue::LWORD(int_representation)
+        | dtp::InitialValue::USINT(int_representation)
+        | dtp::InitialValue::UINT(int_representation)
+        | dtp::InitialValue::UDINT(int_representation)
+        | dtp::InitialValue::ULINT(int_representation)
+        | dtp::InitialValue::SINT(int_representation)
+        | dtp::InitialValue::INT(int_representation)
+        | dtp::InitialValue::DINT(int_representation)
+        | dtp::InitialValue::LINT(int_representation) => match int_representation {
+            dtp::IntRepresentation::SignedDecimal(i64) => i64.to_string(),
+            dtp::IntRepresentation::UnsignedDecimal(u64)
+            | dtp::IntRepresentation::Binary(u64)
+            | dtp::IntRepresentation::Octal(u64)
+            | dtp::IntRepresentation::Heaxdecimal(u64) => u64.to_string(),
+        },
+        dtp::InitialValue::REAL(f32) => f32.to_string(),
+        dtp::InitialValue::LREAL(f64) => f64.to_string(),
+        dtp::InitialValue::CHAR(char_representation) => {
+            format!("{:?}", dtp_char(char_representation))
+        }
+        dtp::InitialValue::STRING(string) => {
+            format!("{:?}", string.iter().map(dtp_char).collect::<String>())
+        }
+        dtp::InitialValue::WSTRING(wstring) => format!(
+            "{:?}",
+            wstring
+                .iter()
+                .map(|wchar_representation| match wchar_representation {
+                    dtp::WcharRepresentation::Wchar(char)
+                    | dtp::WcharRepresentation::Hexadecimal(char) => *char,
+                })
+                .collect::<String>()
+        ),
+        dtp::InitialValue::Array(values) => format!(
+            "[{}]",
+            values.iter().map(dtp_value).collect::<Vec<_>>().join(", ")
+        ),
+    }
+}
+
+fn dtp_char(char_representation: &dtp::CharRepresentation) -> char {
+    match char_representation {
+        dtp::CharRepresentation::Char(char) => *char,
+        dtp::CharRepresentation::Hexadecimal(byte) => char::from(*byte),
+    }
+}
+
+// The order of attributes carries no meaning
+fn dtp_attributes<'a>(attributes: impl IntoIterator<Item = &'a dtp::Attribute>) -> String {
+    let mut attributes: Vec<String> = attributes
+        .into_iter()
+        .map(|attribute| format!("{}={}", attribute.name(), dtp_value(attribute.value())))
+        .collect();
+    attributes.sort();
+    format!("[{}]", attributes.join(", "))
+}
+
+fn is_reference_attribute(attribute: &dtp::Attribute) -> bool {
+    [
+        dtp::ANNOTATION_NAME_ROS2_RELATIVE_REFERENCE,
+        dtp::ANNOTATION_NAME_ROS2_ABSOLUTE_REFERENCE,
+    ]
+    .contains(&attribute.name())
+}
@@ -0,0 +1,99 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use log::warn;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::business::error::{Error, Result};
+use crate::business::file_writer;
+
+pub const SOURCE_MANIFEST_FILE: &str = ".rossydiac-manifest.json";
+
+/// Records the hashes of the source files converted into a directory and the files they were
+/// converted to, so that unchanged source files do not have to be converted again
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SourceManifest {
+    sources: BTreeMap<String, ConvertedSource>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ConvertedSource {
+    hash: String,
+    // the names of the files in the directory
+    outputs: Vec<String>,
+}
+
+impl SourceManifest {
+    /// An unreadable manifest is discarded, which only costs converting every source file again
+    pub fn read(directory: &str) -> Self {
+        let path_to_file = format!("{directory}{SOURCE_MANIFEST_FILE}");
+        if !Path::new(&path_to_file).exists() {
+            return Self::default();
+        }
+        match fs::read_to_string(&path_to_file)
+            .map_err(Error::from)
+            .and_then(|content| serde_json::from_str(&content).map_err(Error::custom))
+        {
+            Ok(manifest) => manifest,
+            Err(error) => {
+                warn!("Ignoring the unreadable manifest {path_to_file:?}: {error}");
+                Self::default()
+            }
+        }
+    }
+
+    pub fn write(&self, directory: &str) -> Result<()> {
+        let content = serde_json::to_string_pretty(self).map_err(Error::custom)?;
+        file_writer::write_if_changed(&format!("{directory}{SOURCE_MANIFEST_FILE}"), content)
+    }
+
+    /// A source file is only up to date as long as none of the files it was converted to is missing
+    pub fn is_up_to_date(&self, directory: &str, path_to_source_file: &str, hash: &str) -> bool {
+        self.sources
+            .get(&key(path_to_source_file))
+            .is_some_and(|source| {
+                source.hash == hash
+                    && source
+                        .outputs
+                        .iter()
+                        .all(|output| Path::new(directory).join(output).exists())
+            })
+    }
+
+    /// The names of the files the source file was last converted to
+    pub fn outputs(&self, path_to_source_file: &str) -> &[String] {
+        self.sources
+            .get(&key(path_to_source_file))
+            .map_or(&[], |source| &source.outputs)
+    }
+
+    pub fn record(&mut self, path_to_source_file: &str, hash: String, paths_to_outputs: &[String]) {
+        let outputs = paths_to_outputs
+            .iter()
+            .map(|path_to_output| {
+                Path::new(path_to_output)
+                    .file_name()
+                    .map_or_else(|| path_to_output.clone(), |name| name.to_string_lossy().into())
+            })
+            .collect();
+        self.sources
+            .insert(key(path_to_source_file), ConvertedSource { hash, outputs });
+    }
+}
+
+// the same source file may be given by different paths, e.g. ./a.msg and a.msg
+fn key(path_to_source_file: &str) -> String {
+    fs::canonicalize(path_to_source_file)
+        .map_or_else(|_| path_to_source_file.to_string(), |path| path.to_string_lossy().into())
+}
+
+/// Hashes the source file together with everything else the conversion result depends on
+pub fn hash(path_to_source_file: &str, settings: &str) -> Result<String> {
+    let mut hasher = Sha256::new();
+    hasher.update(env!("CARGO_PKG_VERSION"));
+    hasher.update(settings);
+    hasher.update(fs::read(path_to_source_file)?);
+    Ok(format!("{:x}", hasher.finalize()))
+}
@@ -0,0 +1,145 @@
+use crate::business::error::Result;
+use crate::core::msg::{BaseType, Field, FieldType};
+
+// Padding materializes every element, so absurd array sizes must not exhaust the memory
+pub const MAX_PADDED_ARRAY_LENGTH: u64 = 65_536;
+
+pub fn validate_fields(fields: &[Field]) -> Result<()> {
+    fields
+        .iter()
+        .filter(|field| *field.field_type() == FieldType::Constant)
+        .try_for_each(validate_constant)
+}
+
+fn validate_constant(field: &Field) -> Result<()> {
+    let name = field.name();
+    if !is_valid_constant_name(name) {
+        return Err(format!(
+            "Constant \"{name}\" is expected to be named in upper case \
+            (e.g. \"MY_CONSTANT\")"
+        )
+        .into());
+    }
+    if field.array_size().is_some() {
+        return Err(format!("Constant \"{name}\" is expected to not be an array").into());
+    }
+    if !is_constant_base_type(field.base_type()) {
+        return Err(format!(
+            "Constant \"{name}\" is expected to be of a primitive or an unbounded string type"
+        )
+        .into());
+    }
+    if field.initial_value().is_none() {
+        return Err(format!("Constant \"{name}\" is expected to have a value").into());
+    }
+    Ok(())
+}
+
+fn is_constant_base_type(base_type: &BaseType) -> bool {
+    !matches!(
+        base_type,
+        BaseType::Custom(_) | BaseType::String(Some(_)) | BaseType::Wstring(Some(_))
+    )
+}
+
+fn is_valid_constant_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_uppercase())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+        && !name.contains("__")
+        && !name.ends_with('_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::msg::{ArraySize, InitialValue, IntRepresentation, Reference};
+
+    fn constant(
+        name: &str,
+        base_type: BaseType,
+        array_size: Option<ArraySize>,
+        initial_value: Option<InitialValue>,
+    ) -> Field {
+        Field::new(
+            name.to_string(),
+            base_type,
+            array_size,
+            FieldType::Constant,
+            initial_value,
+            None,
+            Vec::new(),
+        )
+    }
+
+    fn answer() -> Option<InitialValue> {
+        Some(InitialValue::Int8(IntRepresentation::UnsignedDecimal(42)))
+    }
+
+    #[test]
+    fn accepts_valid_constants_and_any_variables() {
+        assert!(validate_fields(&[
+            constant("ANSWER_2", BaseType::Int8, None, answer()),
+            constant(
+                "TEXT",
+                BaseType::String(None),
+                None,
+                Some(InitialValue::String("text".to_string())),
+            ),
+            Field::new(
+                "lower_case".to_string(),
+                BaseType::String(Some(8)),
+                Some(ArraySize::Dynamic),
+                FieldType::Variable,
+                None,
+                None,
+                Vec::new(),
+            ),
+        ])
+        .is_ok());
+    }
+
+    #[test]
+    fn constants_are_named_in_upper_case() {
+        for name in ["answer", "Answer", "_ANSWER", "2ANSWER", "ANS__WER", "ANSWER_"] {
+            assert!(
+                validate_fields(&[constant(name, BaseType::Int8, None, answer())]).is_err(),
+                "{name}"
+            );
+        }
+    }
+
+    #[test]
+    fn constants_are_no_arrays() {
+        let array = Some(InitialValue::Array(vec![answer().unwrap()]));
+        assert!(validate_fields(&[constant(
+            "ANSWER",
+            BaseType::Int8,
+            Some(ArraySize::Capacity(1)),
+            array
+        )])
+        .is_err());
+    }
+
+    #[test]
+    fn constants_are_of_primitive_or_unbounded_string_types() {
+        let text = || Some(InitialValue::String("text".to_string()));
+        assert!(validate_fields(&[constant("TEXT", BaseType::String(None), None, text())]).is_ok());
+        assert!(
+            validate_fields(&[constant("TEXT", BaseType::String(Some(8)), None, text())]).is_err()
+        );
+        assert!(
+            validate_fields(&[constant("TEXT", BaseType::Wstring(Some(8)), None, text())]).is_err()
+        );
+        let custom = BaseType::Custom(Reference::Relative {
+            file: "Other".to_string(),
+        });
+        assert!(validate_fields(&[constant("OTHER", custom, None, answer())]).is_err());
+    }
+
+    #[test]
+    fn constants_have_a_value() {
+        assert!(validate_fields(&[constant("ANSWER", BaseType::Int8, None, None)]).is_err());
+    }
+}
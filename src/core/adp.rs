@@ -0,0 +1,40 @@
+use crate::core::fbt::{InterfaceList, Service};
+
+#[derive(Clone, Debug)]
+pub struct AdapterType {
+    name: String,
+    comment: Option<String>,
+    interface_list: InterfaceList,
+    service: Service,
+}
+
+impl AdapterType {
+    pub fn new(
+        name: String,
+        comment: Option<String>,
+        interface_list: InterfaceList,
+        service: Service,
+    ) -> Self {
+        Self {
+            name,
+            comment,
+            interface_list,
+            service,
+        }
+    }
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    pub fn comment(&self) -> Option<&String> {
+        self.comment.as_ref()
+    }
+    pub fn interface_list(&self) -> &InterfaceList {
+        &self.interface_list
+    }
+    pub fn service(&self) -> &Service {
+        &self.service
+    }
+}
+
+pub const XML_TAG_ADAPTER_TYPE: &str = "AdapterType";
+pub const XML_VALUE_ADAPTER_STANDARD: &str = "61499-1";
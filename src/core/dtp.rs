@@ -1,17 +1,32 @@
 #![allow(non_camel_case_types)]
 
-#[derive(Clone, Debug)]
+use serde::Serialize;
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub struct DataType {
     name: String,
     comment: Option<String>,
+    identification: Option<Identification>,
+    version_infos: Vec<VersionInfo>,
+    compiler_info: Option<CompilerInfo>,
     structured_type: StructuredType,
 }
 
 impl DataType {
-    pub fn new(name: String, comment: Option<String>, structured_type: StructuredType) -> Self {
+    pub fn new(
+        name: String,
+        comment: Option<String>,
+        identification: Option<Identification>,
+        version_infos: Vec<VersionInfo>,
+        compiler_info: Option<CompilerInfo>,
+        structured_type: StructuredType,
+    ) -> Self {
         Self {
             name,
             comment,
+            identification,
+            version_infos,
+            compiler_info,
             structured_type,
         }
     }
@@ -21,12 +36,191 @@ impl DataType {
     pub fn name(&self) -> &str {
         &self.name
     }
+    pub fn identification(&self) -> Option<&Identification> {
+        self.identification.as_ref()
+    }
+    pub fn version_infos(&self) -> &Vec<VersionInfo> {
+        &self.version_infos
+    }
+    pub fn compiler_info(&self) -> Option<&CompilerInfo> {
+        self.compiler_info.as_ref()
+    }
+    pub fn package_name(&self) -> Option<&String> {
+        self.compiler_info
+            .as_ref()
+            .and_then(|compiler_info| compiler_info.package_name())
+    }
+    pub fn type_name(&self) -> TypeName {
+        TypeName::new(self.package_name().cloned(), self.name.clone())
+    }
     pub fn structured_type(&self) -> &StructuredType {
         &self.structured_type
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Identification {
+    standard: Option<String>,
+    classification: Option<String>,
+    application_domain: Option<String>,
+    function: Option<String>,
+    identification_type: Option<String>,
+    description: Option<String>,
+}
+
+impl Identification {
+    pub fn new(
+        standard: Option<String>,
+        classification: Option<String>,
+        application_domain: Option<String>,
+        function: Option<String>,
+        identification_type: Option<String>,
+        description: Option<String>,
+    ) -> Self {
+        Self {
+            standard,
+            classification,
+            application_domain,
+            function,
+            identification_type,
+            description,
+        }
+    }
+    pub fn standard(&self) -> Option<&String> {
+        self.standard.as_ref()
+    }
+    pub fn classification(&self) -> Option<&String> {
+        self.classification.as_ref()
+    }
+    pub fn application_domain(&self) -> Option<&String> {
+        self.application_domain.as_ref()
+    }
+    pub fn function(&self) -> Option<&String> {
+        self.function.as_ref()
+    }
+    pub fn identification_type(&self) -> Option<&String> {
+        self.identification_type.as_ref()
+    }
+    pub fn description(&self) -> Option<&String> {
+        self.description.as_ref()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct VersionInfo {
+    organization: Option<String>,
+    version: Option<String>,
+    author: Option<String>,
+    date: Option<String>,
+    remarks: Option<String>,
+}
+
+impl VersionInfo {
+    pub fn new(
+        organization: Option<String>,
+        version: Option<String>,
+        author: Option<String>,
+        date: Option<String>,
+        remarks: Option<String>,
+    ) -> Self {
+        Self {
+            organization,
+            version,
+            author,
+            date,
+            remarks,
+        }
+    }
+    pub fn organization(&self) -> Option<&String> {
+        self.organization.as_ref()
+    }
+    pub fn version(&self) -> Option<&String> {
+        self.version.as_ref()
+    }
+    pub fn author(&self) -> Option<&String> {
+        self.author.as_ref()
+    }
+    pub fn date(&self) -> Option<&String> {
+        self.date.as_ref()
+    }
+    pub fn remarks(&self) -> Option<&String> {
+        self.remarks.as_ref()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct CompilerInfo {
+    package_name: Option<String>,
+    header: Option<String>,
+    classdef: Option<String>,
+    compilers: Vec<Compiler>,
+}
+
+impl CompilerInfo {
+    pub fn new(
+        package_name: Option<String>,
+        header: Option<String>,
+        classdef: Option<String>,
+        compilers: Vec<Compiler>,
+    ) -> Self {
+        Self {
+            package_name,
+            header,
+            classdef,
+            compilers,
+        }
+    }
+    pub fn package_name(&self) -> Option<&String> {
+        self.package_name.as_ref()
+    }
+    pub fn header(&self) -> Option<&String> {
+        self.header.as_ref()
+    }
+    pub fn classdef(&self) -> Option<&String> {
+        self.classdef.as_ref()
+    }
+    pub fn compilers(&self) -> &Vec<Compiler> {
+        &self.compilers
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Compiler {
+    language: Option<String>,
+    vendor: Option<String>,
+    product: Option<String>,
+    version: Option<String>,
+}
+
+impl Compiler {
+    pub fn new(
+        language: Option<String>,
+        vendor: Option<String>,
+        product: Option<String>,
+        version: Option<String>,
+    ) -> Self {
+        Self {
+            language,
+            vendor,
+            product,
+            version,
+        }
+    }
+    pub fn language(&self) -> Option<&String> {
+        self.language.as_ref()
+    }
+    pub fn vendor(&self) -> Option<&String> {
+        self.vendor.as_ref()
+    }
+    pub fn product(&self) -> Option<&String> {
+        self.product.as_ref()
+    }
+    pub fn version(&self) -> Option<&String> {
+        self.version.as_ref()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub struct StructuredType {
     comment: Option<String>,
     var_declarations: Vec<VarDeclaration>,
@@ -47,7 +241,7 @@ impl StructuredType {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub struct VarDeclaration {
     name: String,
     base_type: BaseType,
@@ -95,7 +289,7 @@ impl VarDeclaration {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub struct Attribute {
     name: String,
     base_type: BaseType,
@@ -132,7 +326,7 @@ impl Attribute {
 }
 
 #[allow(clippy::upper_case_acronyms)]
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub enum BaseType {
     BOOL,
     BYTE,
@@ -152,17 +346,51 @@ pub enum BaseType {
     CHAR,
     STRING(Option<u64>),
     WSTRING(Option<u64>),
-    Custom(String),
+    Custom(TypeName),
+}
+
+/// A type name, qualified by a package like "ros2::geometry_msgs::Point" since 4diac IDE 3.0
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct TypeName {
+    package: Option<String>,
+    name: String,
+}
+
+impl TypeName {
+    pub fn new(package: Option<String>, name: String) -> Self {
+        Self { package, name }
+    }
+    pub fn parse(qualified_name: &str) -> Self {
+        match qualified_name.rsplit_once(PACKAGE_SEPARATOR) {
+            Some((package, name)) => Self::new(Some(package.to_string()), name.to_string()),
+            None => Self::new(None, qualified_name.to_string()),
+        }
+    }
+    pub fn package(&self) -> Option<&String> {
+        self.package.as_ref()
+    }
+    pub fn name(&self) -> &str {
+        &self.name
+    }
 }
 
-#[derive(Clone, Debug)]
+impl std::fmt::Display for TypeName {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match &self.package {
+            Some(package) => write!(f, "{package}{PACKAGE_SEPARATOR}{}", self.name),
+            None => write!(f, "{}", self.name),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub enum ArraySize {
     Capacity(u64),
     Indexation(i64, i64),
 }
 
 #[allow(clippy::upper_case_acronyms)]
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub enum InitialValue {
     BOOL(BoolRepresentation),
     BYTE(IntRepresentation),
@@ -185,13 +413,13 @@ pub enum InitialValue {
     Array(Vec<InitialValue>),
 }
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub enum BoolRepresentation {
     String(bool),
     Binary(bool),
 }
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub enum IntRepresentation {
     SignedDecimal(i64),
     UnsignedDecimal(u64),
@@ -200,19 +428,24 @@ pub enum IntRepresentation {
     Heaxdecimal(u64),
 }
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub enum CharRepresentation {
     Char(char),
-    Hexadecimal(char),
+    // a CHAR is a single byte, so its hexadecimal notation has exactly 2 digits
+    Hexadecimal(u8),
 }
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub enum WcharRepresentation {
     Wchar(char),
     Hexadecimal(char),
 }
 
 pub const XML_TAG_DATA_TYPE: &str = "DataType";
+pub const XML_TAG_IDENTIFICATION: &str = "Identification";
+pub const XML_TAG_VERSION_INFO: &str = "VersionInfo";
+pub const XML_TAG_COMPILER_INFO: &str = "CompilerInfo";
+pub const XML_TAG_COMPILER: &str = "Compiler";
 pub const XML_TAG_STRUCTURED_TYPE: &str = "StructuredType";
 pub const XML_TAG_VAR_DECLARATION: &str = "VarDeclaration";
 pub const XML_TAG_ATTRIBUTE: &str = "Attribute";
@@ -222,9 +455,35 @@ pub const XML_ATTRIBUTE_ARRAY_SIZE: &str = "ArraySize";
 pub const XML_ATTRIBUTE_INITIAL_VALUE: &str = "InitialValue";
 pub const XML_ATTRIBUTE_VALUE: &str = "Value";
 pub const XML_ATTRIBUTE_COMMENT: &str = "Comment";
+pub const XML_ATTRIBUTE_STANDARD: &str = "Standard";
+pub const XML_ATTRIBUTE_CLASSIFICATION: &str = "Classification";
+pub const XML_ATTRIBUTE_APPLICATION_DOMAIN: &str = "ApplicationDomain";
+pub const XML_ATTRIBUTE_FUNCTION: &str = "Function";
+pub const XML_ATTRIBUTE_DESCRIPTION: &str = "Description";
+pub const XML_ATTRIBUTE_ORGANIZATION: &str = "Organization";
+pub const XML_ATTRIBUTE_VERSION: &str = "Version";
+pub const XML_ATTRIBUTE_AUTHOR: &str = "Author";
+pub const XML_ATTRIBUTE_DATE: &str = "Date";
+pub const XML_ATTRIBUTE_REMARKS: &str = "Remarks";
+pub const XML_ATTRIBUTE_PACKAGE_NAME: &str = "packageName";
+pub const XML_ATTRIBUTE_HEADER: &str = "header";
+pub const XML_ATTRIBUTE_CLASSDEF: &str = "classdef";
+pub const XML_ATTRIBUTE_LANGUAGE: &str = "Language";
+pub const XML_ATTRIBUTE_VENDOR: &str = "Vendor";
+pub const XML_ATTRIBUTE_PRODUCT: &str = "Product";
+pub const XML_VALUE_DATA_TYPE_STANDARD: &str = "1131-3";
+pub const PACKAGE_SEPARATOR: &str = "::";
+pub const PACKAGE_NAME_ROS2: &str = "ros2";
 pub const ANNOTATION_NAME_ROS2_RELATIVE_REFERENCE: &str = "ROS2_RelativeReference";
 pub const ANNOTATION_NAME_ROS2_ABSOLUTE_REFERENCE: &str = "ROS2_AbsoluteReference";
 pub const ANNOTATION_NAME_ROS2_DYNAMIC_ARRAY: &str = "ROS2_DynamicArray";
 pub const ANNOTATION_NAME_ROS2_BOUND_DYNAMIC_ARRAY: &str = "ROS2_BoundDynamicArray";
 pub const ANNOTATION_NAME_ROS2_ELEMENT_COUNTER: &str = "ROS2_ElementCounter";
 pub const ANNOTATION_NAME_ROS2_CONSTANT: &str = "ROS2_Constant";
+pub const ANNOTATION_NAME_ROS2_NAME: &str = "ROS2_Name";
+pub const ANNOTATION_NAME_ROS2_KEY: &str = "ROS2_Key";
+pub const ANNOTATION_NAME_ROS2_UNIT: &str = "ROS2_Unit";
+pub const ANNOTATION_NAME_ROS2_RANGE_MIN: &str = "ROS2_RangeMin";
+pub const ANNOTATION_NAME_ROS2_RANGE_MAX: &str = "ROS2_RangeMax";
+pub const ANNOTATION_NAME_ROS2_DEFAULT: &str = "ROS2_Default";
+pub const ANNOTATION_NAME_ROS2_VERBATIM: &str = "ROS2_Verbatim";
@@ -0,0 +1,219 @@
+use crate::core::dtp::VarDeclaration;
+
+#[derive(Clone, Debug)]
+pub struct FBType {
+    name: String,
+    comment: Option<String>,
+    interface_list: InterfaceList,
+    service: Service,
+}
+
+impl FBType {
+    pub fn new(
+        name: String,
+        comment: Option<String>,
+        interface_list: InterfaceList,
+        service: Service,
+    ) -> Self {
+        Self {
+            name,
+            comment,
+            interface_list,
+            service,
+        }
+    }
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    pub fn comment(&self) -> Option<&String> {
+        self.comment.as_ref()
+    }
+    pub fn interface_list(&self) -> &InterfaceList {
+        &self.interface_list
+    }
+    pub fn service(&self) -> &Service {
+        &self.service
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct InterfaceList {
+    event_inputs: Vec<Event>,
+    event_outputs: Vec<Event>,
+    input_vars: Vec<VarDeclaration>,
+    output_vars: Vec<VarDeclaration>,
+}
+
+impl InterfaceList {
+    pub fn new(
+        event_inputs: Vec<Event>,
+        event_outputs: Vec<Event>,
+        input_vars: Vec<VarDeclaration>,
+        output_vars: Vec<VarDeclaration>,
+    ) -> Self {
+        Self {
+            event_inputs,
+            event_outputs,
+            input_vars,
+            output_vars,
+        }
+    }
+    pub fn event_inputs(&self) -> &Vec<Event> {
+        &self.event_inputs
+    }
+    pub fn event_outputs(&self) -> &Vec<Event> {
+        &self.event_outputs
+    }
+    pub fn input_vars(&self) -> &Vec<VarDeclaration> {
+        &self.input_vars
+    }
+    pub fn output_vars(&self) -> &Vec<VarDeclaration> {
+        &self.output_vars
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Event {
+    name: String,
+    comment: Option<String>,
+    with: Vec<String>,
+}
+
+impl Event {
+    pub fn new(name: String, comment: Option<String>, with: Vec<String>) -> Self {
+        Self {
+            name,
+            comment,
+            with,
+        }
+    }
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    pub fn comment(&self) -> Option<&String> {
+        self.comment.as_ref()
+    }
+    pub fn with(&self) -> &Vec<String> {
+        &self.with
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Service {
+    left_interface: String,
+    right_interface: String,
+    sequences: Vec<ServiceSequence>,
+}
+
+impl Service {
+    pub fn new(
+        left_interface: String,
+        right_interface: String,
+        sequences: Vec<ServiceSequence>,
+    ) -> Self {
+        Self {
+            left_interface,
+            right_interface,
+            sequences,
+        }
+    }
+    pub fn left_interface(&self) -> &str {
+        &self.left_interface
+    }
+    pub fn right_interface(&self) -> &str {
+        &self.right_interface
+    }
+    pub fn sequences(&self) -> &Vec<ServiceSequence> {
+        &self.sequences
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ServiceSequence {
+    name: String,
+    transactions: Vec<ServiceTransaction>,
+}
+
+impl ServiceSequence {
+    pub fn new(name: String, transactions: Vec<ServiceTransaction>) -> Self {
+        Self { name, transactions }
+    }
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    pub fn transactions(&self) -> &Vec<ServiceTransaction> {
+        &self.transactions
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ServiceTransaction {
+    input_primitive: Option<ServicePrimitive>,
+    output_primitives: Vec<ServicePrimitive>,
+}
+
+impl ServiceTransaction {
+    pub fn new(
+        input_primitive: Option<ServicePrimitive>,
+        output_primitives: Vec<ServicePrimitive>,
+    ) -> Self {
+        Self {
+            input_primitive,
+            output_primitives,
+        }
+    }
+    pub fn input_primitive(&self) -> Option<&ServicePrimitive> {
+        self.input_primitive.as_ref()
+    }
+    pub fn output_primitives(&self) -> &Vec<ServicePrimitive> {
+        &self.output_primitives
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ServicePrimitive {
+    interface: String,
+    event: String,
+    parameters: Option<String>,
+}
+
+impl ServicePrimitive {
+    pub fn new(interface: String, event: String, parameters: Option<String>) -> Self {
+        Self {
+            interface,
+            event,
+            parameters,
+        }
+    }
+    pub fn interface(&self) -> &str {
+        &self.interface
+    }
+    pub fn event(&self) -> &str {
+        &self.event
+    }
+    pub fn parameters(&self) -> Option<&String> {
+        self.parameters.as_ref()
+    }
+}
+
+pub const XML_TAG_FB_TYPE: &str = "FBType";
+pub const XML_TAG_INTERFACE_LIST: &str = "InterfaceList";
+pub const XML_TAG_EVENT_INPUTS: &str = "EventInputs";
+pub const XML_TAG_EVENT_OUTPUTS: &str = "EventOutputs";
+pub const XML_TAG_INPUT_VARS: &str = "InputVars";
+pub const XML_TAG_OUTPUT_VARS: &str = "OutputVars";
+pub const XML_TAG_EVENT: &str = "Event";
+pub const XML_TAG_WITH: &str = "With";
+pub const XML_TAG_SERVICE: &str = "Service";
+pub const XML_TAG_SERVICE_SEQUENCE: &str = "ServiceSequence";
+pub const XML_TAG_SERVICE_TRANSACTION: &str = "ServiceTransaction";
+pub const XML_TAG_INPUT_PRIMITIVE: &str = "InputPrimitive";
+pub const XML_TAG_OUTPUT_PRIMITIVE: &str = "OutputPrimitive";
+pub const XML_ATTRIBUTE_VAR: &str = "Var";
+pub const XML_ATTRIBUTE_LEFT_INTERFACE: &str = "LeftInterface";
+pub const XML_ATTRIBUTE_RIGHT_INTERFACE: &str = "RightInterface";
+pub const XML_ATTRIBUTE_INTERFACE: &str = "Interface";
+pub const XML_ATTRIBUTE_EVENT: &str = "Event";
+pub const XML_ATTRIBUTE_PARAMETERS: &str = "Parameters";
+pub const XML_VALUE_EVENT_TYPE: &str = "Event";
+pub const XML_VALUE_SIFB_STANDARD: &str = "61499-2";
@@ -1,2 +1,5 @@
 pub mod msg;
 pub mod dtp;
+pub mod fbt;
+pub mod adp;
+pub mod typelib;
@@ -1,4 +1,6 @@
-#[derive(Debug, Clone)]
+use serde::Serialize;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct StructuredType {
     name: String,
     fields: Vec<Field>,
@@ -16,7 +18,33 @@ impl StructuredType {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Service {
+    name: String,
+    request: StructuredType,
+    response: StructuredType,
+}
+
+impl Service {
+    pub fn new(name: String, request: StructuredType, response: StructuredType) -> Self {
+        Self {
+            name,
+            request,
+            response,
+        }
+    }
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    pub fn request(&self) -> &StructuredType {
+        &self.request
+    }
+    pub fn response(&self) -> &StructuredType {
+        &self.response
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct Field {
     name: String,
     base_type: BaseType,
@@ -24,6 +52,7 @@ pub struct Field {
     field_type: FieldType,
     initial_value: Option<InitialValue>,
     comment: Option<String>,
+    annotations: Vec<Annotation>,
 }
 
 impl Field {
@@ -34,6 +63,7 @@ impl Field {
         field_type: FieldType,
         initial_value: Option<InitialValue>,
         comment: Option<String>,
+        annotations: Vec<Annotation>,
     ) -> Self {
         Self {
             name,
@@ -42,6 +72,7 @@ impl Field {
             field_type,
             initial_value,
             comment,
+            annotations,
         }
     }
     pub fn name(&self) -> &str {
@@ -62,9 +93,12 @@ impl Field {
     pub fn comment(&self) -> Option<&String> {
         self.comment.as_ref()
     }
+    pub fn annotations(&self) -> &Vec<Annotation> {
+        &self.annotations
+    }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub enum BaseType {
     Bool,
     Byte,
@@ -84,26 +118,26 @@ pub enum BaseType {
     Custom(Reference),
 }
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize)]
 pub enum Reference {
     Relative { file: String },
     Absolute { package: String, file: String },
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub enum ArraySize {
     Capacity(u64),
     Dynamic,
     BoundDynamic(u64),
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub enum FieldType {
     Variable,
     Constant,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub enum InitialValue {
     Bool(BoolRepresentation),
     Byte(IntRepresentation),
@@ -123,13 +157,28 @@ pub enum InitialValue {
     Array(Vec<InitialValue>),
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum Annotation {
+    Key,
+    Unit(String),
+    Range {
+        min: Option<InitialValue>,
+        max: Option<InitialValue>,
+    },
+    Default(InitialValue),
+    Verbatim {
+        language: Option<String>,
+        text: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub enum BoolRepresentation {
     String(bool),
     Binary(bool),
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub enum IntRepresentation {
     SignedDecimal(i64),
     UnsignedDecimal(u64),
@@ -138,7 +187,12 @@ pub enum IntRepresentation {
     Hexadecimal(u64),
 }
 
+pub const SERVICE_SEPARATOR: &str = "---";
+pub const SERVICE_REQUEST_SUFFIX: &str = "_Request";
+pub const SERVICE_RESPONSE_SUFFIX: &str = "_Response";
+
 pub const ANNOTATION_NAME_IEC61499_WORD: &str = "IEC61499_WORD";
 pub const ANNOTATION_NAME_IEC61499_DWORD: &str = "IEC61499_DWORD";
 pub const ANNOTATION_NAME_IEC61499_LWORD: &str = "IEC61499_LWORD";
 pub const ANNOTATION_NAME_IEC61499_START_INDEX: &str = "IEC61499_StartIndex";
+pub const ANNOTATION_NAME_IEC61499_NAME: &str = "IEC61499_Name";
@@ -0,0 +1,53 @@
+#[derive(Clone, Debug)]
+pub struct Manifest {
+    name: String,
+    symbolic_name: String,
+    comment: Option<String>,
+    version: String,
+}
+
+impl Manifest {
+    pub fn new(
+        name: String,
+        symbolic_name: String,
+        comment: Option<String>,
+        version: String,
+    ) -> Self {
+        Self {
+            name,
+            symbolic_name,
+            comment,
+            version,
+        }
+    }
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    pub fn symbolic_name(&self) -> &str {
+        &self.symbolic_name
+    }
+    pub fn comment(&self) -> Option<&String> {
+        self.comment.as_ref()
+    }
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+}
+
+pub const TYPELIB_DIRECTORY: &str = "typelib";
+pub const MANIFEST_FILE: &str = ".MANIFEST";
+pub const PROJECT_FILE: &str = ".project";
+pub const XML_TAG_MANIFEST: &str = "Manifest";
+pub const XML_TAG_PRODUCT: &str = "Product";
+pub const XML_TAG_DEPENDENCIES: &str = "Dependencies";
+pub const XML_TAG_PROJECT_DESCRIPTION: &str = "projectDescription";
+pub const XML_TAG_NAME: &str = "name";
+pub const XML_TAG_COMMENT: &str = "comment";
+pub const XML_TAG_PROJECTS: &str = "projects";
+pub const XML_TAG_BUILD_SPEC: &str = "buildSpec";
+pub const XML_TAG_NATURES: &str = "natures";
+pub const XML_TAG_NATURE: &str = "nature";
+pub const XML_ATTRIBUTE_SCOPE: &str = "Scope";
+pub const XML_ATTRIBUTE_SYMBOLIC_NAME: &str = "SymbolicName";
+pub const XML_VALUE_LIBRARY_SCOPE: &str = "Library";
+pub const XML_VALUE_FORDIAC_NATURE: &str = "org.eclipse.fordiac.ide.systemmanagement.FordiacNature";
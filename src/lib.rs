@@ -0,0 +1,2 @@
+pub mod business;
+pub mod core;
@@ -0,0 +1,174 @@
+//! Runs every fixture in `test/0-msg` and `test/0-dtp` through both converters and compares the
+//! results with the expected outputs in `tests/golden`. The JSON printed for them and for the
+//! fixtures in `test/0-print` is compared as well, as it exposes the structure of the read files.
+//!
+//! Run `BLESS=1 cargo test --test golden` to replace the expected outputs with the current ones.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const PACKAGE_NAME: &str = "conversion_tests";
+const BLESS: &str = "BLESS";
+
+#[test]
+fn msg_fixtures_convert_to_expected_dtp_files_and_back() {
+    let actual_directory = actual_directory("msg");
+    let dtp_directory = actual_directory.join("1-dtp");
+    let msg_directory = actual_directory.join("2-msg");
+    for msg_file in files_in(&fixture_directory("0-msg")) {
+        run("convert-to-dtp", &msg_file, &dtp_directory);
+    }
+    for dtp_file in files_in(&dtp_directory) {
+        run("convert-to-msg", &dtp_file, &msg_directory);
+    }
+    check("1-dtp", &dtp_directory);
+    check("2-msg", &msg_directory);
+}
+
+#[test]
+fn dtp_fixtures_convert_to_expected_msg_files_and_back() {
+    let actual_directory = actual_directory("dtp");
+    let msg_directory = actual_directory.join("1-msg");
+    let dtp_directory = actual_directory.join("2-dtp");
+    for dtp_file in files_in(&fixture_directory("0-dtp")) {
+        run("convert-to-msg", &dtp_file, &msg_directory);
+    }
+    for msg_file in files_in(&msg_directory) {
+        run("convert-to-dtp", &msg_file, &dtp_directory);
+    }
+    check("1-msg", &msg_directory);
+    check("2-dtp", &dtp_directory);
+}
+
+#[test]
+fn fixtures_print_expected_json() {
+    let actual_directory = actual_directory("print");
+    for (fixtures, subcommand) in [
+        ("0-msg", "print-msg"),
+        ("0-dtp", "print-dtp"),
+        ("0-print", "print-msg"),
+    ] {
+        for source_file in files_in(&fixture_directory(fixtures)) {
+            print(subcommand, &source_file, &actual_directory);
+        }
+    }
+    check("print", &actual_directory);
+}
+
+fn fixture_directory(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("test")
+        .join(name)
+}
+
+fn expected_directory(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("golden")
+        .join(name)
+}
+
+fn actual_directory(name: &str) -> PathBuf {
+    let directory = Path::new(env!("CARGO_TARGET_TMPDIR"))
+        .join("golden")
+        .join(name);
+    if directory.exists() {
+        fs::remove_dir_all(&directory).unwrap();
+    }
+    directory
+}
+
+fn files_in(directory: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = fs::read_dir(directory)
+        .unwrap_or_else(|error| panic!("{} is not readable: {error}", directory.display()))
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.is_file())
+        .collect();
+    files.sort();
+    files
+}
+
+fn run(subcommand: &str, source_file: &Path, destination_directory: &Path) {
+    fs::create_dir_all(destination_directory).unwrap();
+    // the destination directory is concatenated with the file name, so it needs a trailing separator
+    let destination_directory = format!("{}/", destination_directory.display());
+    let output = Command::new(env!("CARGO_BIN_EXE_RossyDiac"))
+        .args([
+            subcommand,
+            "-p",
+            PACKAGE_NAME,
+            "-d",
+            &destination_directory,
+            "-f",
+        ])
+        .arg(source_file)
+        // pins the date in the generated version infos
+        .env("SOURCE_DATE_EPOCH", "0")
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{subcommand} {} failed:\n{}",
+        source_file.display(),
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+// writes the printed JSON next to the other results, named after the source file
+fn print(subcommand: &str, source_file: &Path, destination_directory: &Path) {
+    fs::create_dir_all(destination_directory).unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_RossyDiac"))
+        .args([subcommand, "-f"])
+        .arg(source_file)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{subcommand} {} failed:\n{}",
+        source_file.display(),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let file_name = format!("{}.json", source_file.file_name().unwrap().to_string_lossy());
+    fs::write(destination_directory.join(file_name), output.stdout).unwrap();
+}
+
+fn check(name: &str, actual_directory: &Path) {
+    let expected_directory = expected_directory(name);
+    if env::var_os(BLESS).is_some() {
+        if expected_directory.exists() {
+            fs::remove_dir_all(&expected_directory).unwrap();
+        }
+        fs::create_dir_all(&expected_directory).unwrap();
+        for actual_file in files_in(actual_directory) {
+            fs::copy(
+                &actual_file,
+                expected_directory.join(actual_file.file_name().unwrap()),
+            )
+            .unwrap();
+        }
+        return;
+    }
+
+    let file_names = |directory: &Path| -> Vec<String> {
+        files_in(directory)
+            .iter()
+            .map(|file| file.file_name().unwrap().to_string_lossy().to_string())
+            .collect()
+    };
+    assert_eq!(
+        file_names(actual_directory),
+        file_names(&expected_directory),
+        "the files written to {name} differ, run with {BLESS}=1 if this is intended"
+    );
+    for file_name in file_names(&expected_directory) {
+        let actual = fs::read_to_string(actual_directory.join(&file_name)).unwrap();
+        let expected = fs::read_to_string(expected_directory.join(&file_name)).unwrap();
+        assert!(
+            actual == expected,
+            "{name}/{file_name} differs from the expected output, run with {BLESS}=1 if this is intended\n\
+             --- expected\n{expected}\n+++ actual\n{actual}"
+        );
+    }
+}